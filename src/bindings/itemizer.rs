@@ -1,15 +1,33 @@
+use std::collections::BTreeMap;
+
+use reqwest::{
+    header::{HeaderMap, CONTENT_TYPE},
+    StatusCode,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{FeedOption, PseudoItem};
 
 /// Json arguments for the scraper script
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct ItemizerArg {
     /// URL of origin
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// String returned from origin
     pub webstr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Base64 encoded non-text response, only if feed.binary = base64
+    pub webbase64: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Path to a file containing the non-text response, only if feed.binary = file
+    pub webfile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Response details of the request, only if fetched
+    pub response: Option<ItemizerResponse>,
+    /// Resolved auth secret, only if feed.expose-secret = true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
     /// Items to ignore when extracting
     pub preexists: Vec<PseudoItem>,
     /// Items left to scrap
@@ -19,6 +37,121 @@ pub struct ItemizerArg {
     pub feed: FeedOption,
 }
 
+impl ItemizerArg {
+    /// Output with the items that are not in preexists, up to the number of items left
+    pub fn output(
+        &self,
+        items: impl IntoIterator<Item = PseudoItem>,
+        continuation: Option<String>,
+    ) -> ItemizerRes {
+        let (known, items): (Vec<PseudoItem>, Vec<PseudoItem>) = items
+            .into_iter()
+            .partition(|item| self.preexists.contains(item));
+
+        ItemizerRes {
+            continuation,
+            known: known.len(),
+            items: items.into_iter().take(self.length_left as usize).collect(),
+        }
+    }
+
+    /// URL to resolve relative links against, after following redirects
+    pub fn base_url(&self) -> &str {
+        self.response
+            .as_ref()
+            .map_or(self.url.as_str(), |response| response.url.as_str())
+    }
+}
+
+#[cfg(test)]
+impl ItemizerArg {
+    /// Argument for a page at url that was not fetched by the crate, for testing extractors
+    pub(crate) fn page(url: &str, webstr: &str, feed: FeedOption) -> Self {
+        Self {
+            url: url.to_string(),
+            webstr: Some(webstr.to_string()),
+            webbase64: None,
+            webfile: None,
+            response: None,
+            secret: None,
+            preexists: Vec::new(),
+            length_left: 10,
+            feed,
+        }
+    }
+}
+
+/// HTTP response details for the scraper script
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ItemizerResponse {
+    /// URL after following redirects
+    pub url: String,
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers, repeated headers are joined by ", "
+    pub headers: BTreeMap<String, String>,
+    /// Mime type from the Content-Type header, or detected from the content
+    #[serde(rename = "contentType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+impl ItemizerResponse {
+    pub fn new(url: String, status: StatusCode, headers: &HeaderMap, content: &[u8]) -> Self {
+        let mut map: BTreeMap<String, String> = BTreeMap::new();
+        for (key, value) in headers.iter() {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            map.entry(key.to_string())
+                .and_modify(|existing| {
+                    existing.push_str(", ");
+                    existing.push_str(&value)
+                })
+                .or_insert_with(|| value.to_string());
+        }
+
+        let content_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|mime| mime.trim().to_lowercase())
+            .filter(|mime| !mime.is_empty())
+            .or_else(|| sniff_content_type(content).map(str::to_string));
+
+        Self {
+            url,
+            status: status.as_u16(),
+            headers: map,
+            content_type,
+        }
+    }
+}
+
+/// Guess the mime type of content from how it starts
+fn sniff_content_type(content: &[u8]) -> Option<&'static str> {
+    let start = String::from_utf8_lossy(&content[..content.len().min(256)]);
+    let start = start
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .chars()
+        .take(256)
+        .collect::<String>()
+        .to_lowercase();
+
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        Some("text/html")
+    } else if start.contains("<rss") {
+        Some("application/rss+xml")
+    } else if start.contains("<feed") && start.contains("http://www.w3.org/2005/atom") {
+        Some("application/atom+xml")
+    } else if start.starts_with("<?xml") {
+        Some("application/xml")
+    } else if start.starts_with('{') || start.starts_with('[') {
+        Some("application/json")
+    } else {
+        None
+    }
+}
+
 /// Json response expected from the scraper script
 #[derive(Serialize, Deserialize)]
 pub struct ItemizerRes {
@@ -27,4 +160,7 @@ pub struct ItemizerRes {
     pub continuation: Option<String>,
     /// Parsed items
     pub items: Vec<PseudoItem>,
+    /// Number of items left out because they were in preexists
+    #[serde(default)]
+    pub known: usize,
 }
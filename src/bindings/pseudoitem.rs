@@ -1,5 +1,7 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, error::Error};
 
+use chrono::DateTime;
+use reqwest::Url;
 use rss::{extension::ExtensionMap, *};
 use serde::{Deserialize, Serialize};
 
@@ -158,6 +160,63 @@ pub struct PseudoItem {
     // pub dublin_core_ext: Option<DublinCoreExtension>
 }
 
+impl PseudoItem {
+    /// Set the publish date, RFC 3339 dates and unix timestamps in seconds are converted to RFC 2822
+    pub fn set_date(&mut self, date: &str) {
+        let date = date.trim();
+        let converted = match DateTime::parse_from_rfc3339(date) {
+            Ok(date) => Some(date.to_rfc2822()),
+            Err(_) => date
+                .parse()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                .map(|date| date.to_rfc2822()),
+        };
+
+        self.pub_date = Some(converted.unwrap_or_else(|| date.to_string()));
+    }
+
+    /// Item from the values of each field by its name in feeds.json, i.e. "pubDate", none without a title or link
+    pub fn from_fields(
+        base: &Url,
+        mut values: impl FnMut(&str) -> Result<Vec<String>, Box<dyn Error>>,
+    ) -> Result<Option<Self>, Box<dyn Error>> {
+        let mut first = |name: &str| -> Result<Option<String>, Box<dyn Error>> {
+            Ok(values(name)?.into_iter().next())
+        };
+
+        let mut item = Self {
+            title: first("title")?,
+            link: match first("link")? {
+                Some(link) => Some(base.join(&link)?.to_string()),
+                None => None,
+            },
+            description: first("description")?,
+            author: first("author")?,
+            comments: first("comments")?,
+            guid: first("guid")?.map(|value| PseudoGuid {
+                value,
+                permalink: false,
+            }),
+            content: first("content")?,
+            ..Default::default()
+        };
+
+        if let Some(date) = first("pubDate")? {
+            item.set_date(&date);
+        }
+
+        let categories: Vec<PseudoCategory> = values("category")?
+            .into_iter()
+            .map(|name| PseudoCategory { name, domain: None })
+            .collect();
+        item.categories = Some(categories).filter(|categories| !categories.is_empty());
+
+        // items without either cannot be told apart
+        Ok(Some(item).filter(|item| item.title.is_some() || item.link.is_some()))
+    }
+}
+
 impl PartialEq for PseudoItem {
     fn eq(&self, other: &Self) -> bool {
         (self.link.is_some() && self.link == other.link)
@@ -191,6 +250,33 @@ impl From<PseudoItem> for Item {
     }
 }
 
+impl From<Item> for PseudoItem {
+    fn from(val: Item) -> Self {
+        let mut item = PseudoItem {
+            title: val.title,
+            link: val.link,
+            description: val.description,
+            author: val.author,
+            categories: Some(val.categories)
+                .filter(|categories| !categories.is_empty())
+                .map(|categories| categories.into_iter().map(Category::into).collect()),
+            comments: val.comments,
+            enclosure: val.enclosure.map(Enclosure::into),
+            guid: val.guid.map(Guid::into),
+            pub_date: None,
+            timestamp: None,
+            source: val.source.map(Source::into),
+            content: val.content,
+        };
+
+        if let Some(pub_date) = val.pub_date {
+            item.set_date(&pub_date);
+        }
+
+        item
+    }
+}
+
 /// Serde impled version of rss::Category
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PseudoCategory {
@@ -208,6 +294,15 @@ impl From<PseudoCategory> for Category {
     }
 }
 
+impl From<Category> for PseudoCategory {
+    fn from(val: Category) -> Self {
+        PseudoCategory {
+            name: val.name,
+            domain: val.domain,
+        }
+    }
+}
+
 /// Serde impled version of rss::Enclosure
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PseudoEnclosure {
@@ -227,6 +322,16 @@ impl From<PseudoEnclosure> for Enclosure {
     }
 }
 
+impl From<Enclosure> for PseudoEnclosure {
+    fn from(val: Enclosure) -> Self {
+        PseudoEnclosure {
+            url: val.url,
+            length: val.length,
+            mime_type: val.mime_type,
+        }
+    }
+}
+
 /// Serde impled version of rss::Guid
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PseudoGuid {
@@ -243,6 +348,15 @@ impl From<PseudoGuid> for Guid {
     }
 }
 
+impl From<Guid> for PseudoGuid {
+    fn from(val: Guid) -> Self {
+        PseudoGuid {
+            value: val.value,
+            permalink: val.permalink,
+        }
+    }
+}
+
 /// Serde impled version of rss::Source
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PseudoSource {
@@ -260,6 +374,15 @@ impl From<PseudoSource> for Source {
     }
 }
 
+impl From<Source> for PseudoSource {
+    fn from(val: Source) -> Self {
+        PseudoSource {
+            url: val.url,
+            title: val.title,
+        }
+    }
+}
+
 /// Serde impled version of rss::Cloud
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PseudoCloud {
@@ -329,3 +452,78 @@ impl From<PseudoTextInput> for TextInput {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use reqwest::Url;
+
+    use super::PseudoItem;
+
+    fn from_fields(fields: &[(&str, &[&str])]) -> Option<PseudoItem> {
+        let base = Url::parse("https://example.com/blog/").unwrap();
+        let fields: HashMap<&str, &[&str]> = fields.iter().copied().collect();
+
+        PseudoItem::from_fields(&base, |name| {
+            Ok(fields
+                .get(name)
+                .map(|values| values.iter().map(|value| value.to_string()).collect())
+                .unwrap_or_default())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn fields_take_the_first_value_and_every_category() {
+        let item = from_fields(&[
+            ("title", &["First", "ignored"]),
+            ("link", &["../posts/1"]),
+            ("guid", &["1"]),
+            ("category", &["a", "b"]),
+        ])
+        .unwrap();
+
+        assert_eq!(item.title.as_deref(), Some("First"));
+        assert_eq!(item.link.as_deref(), Some("https://example.com/posts/1"));
+        let guid = item.guid.unwrap();
+        assert_eq!(guid.value, "1");
+        assert!(!guid.permalink);
+        let categories: Vec<_> = item
+            .categories
+            .iter()
+            .flatten()
+            .map(|category| category.name.as_str())
+            .collect();
+        assert_eq!(categories, ["a", "b"]);
+    }
+
+    #[test]
+    fn items_need_a_title_or_link() {
+        assert!(from_fields(&[("description", &["d"]), ("category", &["a"])]).is_none());
+        assert!(from_fields(&[("link", &["/1"])]).is_some());
+        assert!(from_fields(&[]).is_none());
+    }
+
+    #[test]
+    fn dates_are_converted_to_rfc2822() {
+        let date = |date: &str| {
+            from_fields(&[("title", &["t"]), ("pubDate", &[date])])
+                .unwrap()
+                .pub_date
+        };
+
+        assert_eq!(
+            date("2024-05-01T12:00:00+02:00").as_deref(),
+            Some("Wed, 1 May 2024 12:00:00 +0200")
+        );
+        assert_eq!(
+            date("1714557600").as_deref(),
+            Some("Wed, 1 May 2024 10:00:00 +0000")
+        );
+        assert_eq!(
+            date("Wed, 01 May 2024 10:00:00 GMT").as_deref(),
+            Some("Wed, 01 May 2024 10:00:00 GMT")
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+/// Number of bytes to look through for a charset declaration
+const SNIFF_LENGTH: usize = 1024;
+
+/// Decode a response body, the charset is taken from the BOM, the Content-Type header,
+/// then `<meta>` or `<?xml?>` declarations, falling back to UTF-8
+pub fn decode(bytes: &[u8], content_type: Option<&str>) -> String {
+    let encoding = Encoding::for_bom(bytes)
+        .map(|(encoding, _)| encoding)
+        .or_else(|| content_type.and_then(header_charset))
+        .or_else(|| sniff_charset(bytes))
+        .unwrap_or(UTF_8);
+
+    encoding.decode(bytes).0.into_owned()
+}
+
+/// Check if a response is text, either from its mime type or by looking for null bytes
+pub fn is_text(mime: Option<&str>, bytes: &[u8]) -> bool {
+    match mime {
+        Some(mime) => {
+            mime.starts_with("text/")
+                || mime.ends_with("+xml")
+                || mime.ends_with("+json")
+                || matches!(
+                    mime,
+                    "application/json"
+                        | "application/xml"
+                        | "application/javascript"
+                        | "application/ecmascript"
+                        | "application/x-www-form-urlencoded"
+                )
+        }
+        None => !bytes[..bytes.len().min(SNIFF_LENGTH)].contains(&0),
+    }
+}
+
+/// Charset parameter of a Content-Type header value
+fn header_charset(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        if !key.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+
+        Encoding::for_label(value.trim().trim_matches(['"', '\'']).as_bytes())
+    })
+}
+
+/// Charset declared by `<meta charset>`, `<meta http-equiv>` or `<?xml encoding?>`
+fn sniff_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(SNIFF_LENGTH)]).to_lowercase();
+
+    let label = if head.trim_start().starts_with("<?xml") {
+        let decl = &head[..head.find("?>")?];
+        attribute_value(decl, "encoding")
+    } else {
+        head.split("<meta")
+            .skip(1)
+            .find_map(|tag| attribute_value(&tag[..tag.find('>')?], "charset"))
+    }?;
+
+    // a document that could be read as ascii cannot actually be utf-16
+    match Encoding::for_label(label.as_bytes())? {
+        encoding if encoding == UTF_16LE || encoding == UTF_16BE => Some(UTF_8),
+        encoding => Some(encoding),
+    }
+}
+
+/// Value following `key=` in a tag, with or without quotes
+fn attribute_value<'a>(tag: &'a str, key: &str) -> Option<&'a str> {
+    let value = tag[tag.find(key)? + key.len()..].trim_start();
+    let value = value.strip_prefix('=')?.trim_start();
+    let value = value.trim_start_matches(['"', '\'']);
+    let end = value
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')))
+        .unwrap_or(value.len());
+
+    Some(&value[..end]).filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use encoding_rs::{SHIFT_JIS, UTF_8, WINDOWS_1252};
+
+    use super::{decode, is_text, sniff_charset};
+
+    #[test]
+    fn sniffs_meta_and_xml_declarations() {
+        assert_eq!(
+            sniff_charset(b"<html><meta charset=\"Shift_JIS\">"),
+            Some(SHIFT_JIS)
+        );
+        assert_eq!(
+            sniff_charset(
+                b"<META http-equiv=Content-Type content='text/html; charset=iso-8859-1'>"
+            ),
+            Some(WINDOWS_1252)
+        );
+        assert_eq!(
+            sniff_charset(b"<?xml version=\"1.0\" encoding='windows-1252'?><rss/>"),
+            Some(WINDOWS_1252)
+        );
+        assert_eq!(sniff_charset(b"<meta charset=\"utf-16\">"), Some(UTF_8));
+        assert_eq!(
+            sniff_charset(b"<meta name=\"x\"><p>charset=latin1</p>"),
+            None
+        );
+        assert_eq!(sniff_charset(b"<meta charset=\"nonsense\">"), None);
+    }
+
+    #[test]
+    fn decodes_by_bom_then_header_then_declaration() {
+        let latin1 = b"<meta charset=\"iso-8859-1\">caf\xe9";
+        assert_eq!(decode(latin1, None), "<meta charset=\"iso-8859-1\">café");
+        assert_eq!(
+            decode(b"caf\xe9", Some("text/html; charset=\"windows-1252\"")),
+            "café"
+        );
+        // the header wins over the declaration
+        assert_eq!(
+            decode(
+                "<meta charset=\"iso-8859-1\">café".as_bytes(),
+                Some("text/html; charset=utf-8")
+            ),
+            "<meta charset=\"iso-8859-1\">café"
+        );
+        // the bom wins over the header
+        assert_eq!(
+            decode(
+                b"\xef\xbb\xbfcaf\xc3\xa9",
+                Some("text/html; charset=latin1")
+            ),
+            "café"
+        );
+        assert_eq!(decode(b"\xff\xfeh\0i\0", None), "hi");
+        assert_eq!(decode("café".as_bytes(), None), "café");
+    }
+
+    #[test]
+    fn detects_text_by_mime_or_null_bytes() {
+        assert!(is_text(Some("text/plain"), b"\0"));
+        assert!(is_text(Some("application/atom+xml"), b""));
+        assert!(!is_text(Some("application/pdf"), b"text"));
+        assert!(is_text(None, b"plain"));
+        assert!(!is_text(None, b"\x89PNG\0"));
+    }
+}
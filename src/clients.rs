@@ -0,0 +1,39 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Mutex, OnceLock},
+};
+
+use reqwest::Client;
+
+/// HTTP clients built for feeds with their own options
+pub struct Clients(pub OnceLock<Mutex<HashMap<String, Client>>>);
+
+impl Clients {
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Get the client under a key, building one if it does not exist
+    pub fn get(
+        &self,
+        key: &str,
+        build: impl FnOnce() -> Result<Client, Box<dyn Error>>,
+    ) -> Result<Client, Box<dyn Error>> {
+        let mut clients = self.0.get_or_init(Default::default).lock().unwrap();
+
+        if let Some(client) = clients.get(key) {
+            return Ok(client.clone());
+        }
+
+        let client = build()?;
+        clients.insert(key.to_string(), client.clone());
+        Ok(client)
+    }
+}
+
+impl Default for Clients {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,87 @@
+use std::{error::Error, path::PathBuf, sync::Mutex};
+
+use cookie_store::{Cookie, CookieStore};
+use reqwest::{header::HeaderValue, Url};
+
+use crate::options::CookieOption;
+
+/// Cookie jar of a feed, optionally persisted to a file
+pub struct CookieJar {
+    store: Mutex<CookieStore>,
+    path: Option<PathBuf>,
+}
+
+impl CookieJar {
+    /// Load saved cookies from path if persisted, and add the initial cookies
+    pub fn load(
+        path: PathBuf,
+        origin: &str,
+        option: &CookieOption,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut store = if option.persist && path.exists() {
+            let cookies: Vec<Cookie<'static>> =
+                serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            CookieStore::from_cookies(cookies.into_iter().map(Ok::<_, ()>), false).unwrap()
+        } else {
+            CookieStore::default()
+        };
+
+        let origin = Url::parse(origin)?;
+        for cookie in option.initial.iter() {
+            store.parse(cookie, &origin)?;
+        }
+
+        Ok(Self {
+            store: Mutex::new(store),
+            path: option.persist.then_some(path),
+        })
+    }
+
+    /// Write unexpired cookies to file
+    fn save(&self, store: &CookieStore) -> Result<(), Box<dyn Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let cookies: Vec<&Cookie> = store.iter_unexpired().collect();
+        std::fs::write(path, serde_json::to_string(&cookies)?)?;
+        Ok(())
+    }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let mut store = self.store.lock().unwrap();
+        for header in cookie_headers {
+            if let Ok(cookie) = header.to_str() {
+                let _ = store.parse(cookie, url);
+            }
+        }
+
+        // cookie files are small and only written when the server sets cookies
+        if let Err(e) = self.save(&store) {
+            println!("Could not save cookies: {e}");
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let cookies = self
+            .store
+            .lock()
+            .unwrap()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if cookies.is_empty() {
+            return None;
+        }
+
+        HeaderValue::from_str(&cookies).ok()
+    }
+}
@@ -4,6 +4,38 @@ use std::fmt::Display;
 pub enum Error {
     Timedout,
     FetchFailed,
+    /// URL is disallowed by robots.txt
+    RobotsDisallowed(String),
+    /// Secret could not be read from the environment or file
+    MissingSecret(String),
+    /// Redirect to a host that is not an origin of a feed with auth
+    CrossHostRedirect(String),
+    /// No recorded response for the URL in replay mode
+    MissingFixture(String),
+    /// No files at a file:// origin, or none matching its glob
+    MissingFiles(String),
+    /// file:// URL linked from a page of an origin that is not a local file
+    LocalFileDenied(String),
+    /// Response body of the URL is larger than the limit
+    BodyTooLarge {
+        url: String,
+        limit: u64,
+    },
+    /// Page at url links to a page already fetched from the same origin
+    ContinuationLoop {
+        from: String,
+        url: String,
+    },
+    /// No extractor is registered under the name
+    UnknownExtractor(String),
+    /// Feed has no options for its built-in extractor
+    MissingOption(String),
+    /// Server responded with a non-2xx status
+    HttpStatus {
+        status: u16,
+        /// Seconds to wait from the Retry-After header
+        retry_after: Option<u64>,
+    },
 }
 
 impl Display for Error {
@@ -13,3 +45,11 @@ impl Display for Error {
 }
 
 impl std::error::Error for Error {}
+
+/// Error that can be held across an await or sent between threads, keeping errors from this crate intact
+pub(crate) fn sendable(e: Box<dyn std::error::Error>) -> Box<dyn std::error::Error + Send + Sync> {
+    match e.downcast::<Error>() {
+        Ok(e) => e,
+        Err(e) => e.to_string().into(),
+    }
+}
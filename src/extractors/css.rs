@@ -0,0 +1,171 @@
+use std::error::Error;
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{errors::sendable, CssField, Extractor, ItemizerArg, ItemizerRes, PseudoItem};
+
+/// Built-in extractor mapping CSS selectors onto item fields, configured by feed.css
+pub struct CssExtractor;
+
+#[async_trait::async_trait]
+impl Extractor for CssExtractor {
+    async fn extract(&self, arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+        let arg = arg.clone();
+        // large pages are parsed off the async runtime, so the script timeout can still fire
+        tokio::task::spawn_blocking(move || extract(&arg).map_err(sendable))
+            .await?
+            .map_err(|e| e as Box<dyn Error>)
+    }
+}
+
+/// Items matching the CSS selectors of feed.css
+fn extract(arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+    let Some(option) = &arg.feed.css else {
+        return Err(crate::Error::MissingOption("css".to_string()).into());
+    };
+    let html = Html::parse_document(arg.webstr.as_deref().unwrap_or_default());
+    let base = Url::parse(arg.base_url())?;
+
+    let mut items = Vec::new();
+    for element in html.select(&selector(&option.item)?) {
+        items.extend(PseudoItem::from_fields(&base, |name| {
+            match option.fields.get(name) {
+                Some(field) => values(field, element),
+                None => Ok(Vec::new()),
+            }
+        })?);
+    }
+
+    let continuation = match &option.next {
+        Some(next) => html
+            .select(&selector(next)?)
+            .find_map(|element| element.value().attr("href"))
+            .map(|href| base.join(href))
+            .transpose()?
+            .map(String::from),
+        None => None,
+    };
+
+    Ok(arg.output(items, continuation))
+}
+
+fn selector(selector: &str) -> Result<Selector, Box<dyn Error>> {
+    Ok(Selector::parse(selector).map_err(|e| format!("invalid selector {selector}: {e}"))?)
+}
+
+/// Values of a field in an item, one for each element matched
+fn values(field: &CssField, item: ElementRef) -> Result<Vec<String>, Box<dyn Error>> {
+    let elements = match &field.selector {
+        Some(field) => item.select(&selector(field)?).collect(),
+        None => vec![item],
+    };
+
+    Ok(elements
+        .into_iter()
+        .filter_map(|element| match &field.attr {
+            Some(attr) => element.value().attr(attr).map(str::to_string),
+            None if field.html => Some(element.inner_html()),
+            None => Some(element.text().collect()),
+        })
+        .map(|value| value.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|value| !value.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract;
+    use crate::{CssField, CssOption, FeedOption, ItemFields, ItemizerArg, PseudoItem};
+
+    const PAGE: &str = r#"
+        <article>
+            <h2> First
+                post </h2>
+            <a href="/posts/1">more</a>
+            <span class="tag">a</span><span class="tag">b</span>
+            <time datetime="2024-05-01T10:00:00Z">May 1</time>
+        </article>
+        <article><h2>Second</h2><a href="posts/2">more</a></article>
+        <article><p>neither a title nor a link</p></article>
+        <a rel="next" href="?page=2">next</a>
+    "#;
+
+    fn feed() -> FeedOption {
+        let field = |selector: &str, attr: Option<&str>| CssField {
+            selector: Some(selector.to_string()),
+            attr: attr.map(str::to_string),
+            ..Default::default()
+        };
+
+        FeedOption {
+            css: Some(CssOption {
+                item: "article".to_string(),
+                next: Some("a[rel=next]".to_string()),
+                fields: ItemFields {
+                    title: Some(field("h2", None)),
+                    link: Some(field("a", Some("href"))),
+                    categories: Some(field(".tag", None)),
+                    pub_date: Some(field("time", Some("datetime"))),
+                    ..Default::default()
+                },
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fields_are_mapped_and_links_resolved() {
+        let arg = ItemizerArg::page("https://example.com/blog/", PAGE, feed());
+        let res = extract(&arg).unwrap();
+
+        let titles: Vec<_> = res.items.iter().map(|item| item.title.as_deref()).collect();
+        assert_eq!(titles, [Some("First post"), Some("Second")]);
+        let links: Vec<_> = res.items.iter().map(|item| item.link.as_deref()).collect();
+        assert_eq!(
+            links,
+            [
+                Some("https://example.com/posts/1"),
+                Some("https://example.com/blog/posts/2")
+            ]
+        );
+
+        let categories: Vec<_> = res.items[0]
+            .categories
+            .iter()
+            .flatten()
+            .map(|category| category.name.as_str())
+            .collect();
+        assert_eq!(categories, ["a", "b"]);
+        assert_eq!(
+            res.items[0].pub_date.as_deref(),
+            Some("Wed, 1 May 2024 10:00:00 +0000")
+        );
+        assert!(res.items[1].categories.is_none());
+
+        assert_eq!(
+            res.continuation.as_deref(),
+            Some("https://example.com/blog/?page=2")
+        );
+    }
+
+    #[test]
+    fn preexisting_items_are_counted_as_known() {
+        let mut arg = ItemizerArg::page("https://example.com/blog/", PAGE, feed());
+        arg.preexists = vec![PseudoItem {
+            title: Some("Second".to_string()),
+            ..Default::default()
+        }];
+        let res = extract(&arg).unwrap();
+
+        assert_eq!(res.items.len(), 1);
+        assert_eq!(res.items[0].title.as_deref(), Some("First post"));
+        assert_eq!(res.known, 1);
+
+        arg.preexists.clear();
+        arg.length_left = 1;
+        let res = extract(&arg).unwrap();
+        assert_eq!(res.items.len(), 1);
+        assert_eq!(res.known, 0);
+    }
+}
@@ -0,0 +1,348 @@
+use std::error::Error;
+
+use atom_syndication::{Entry, Feed};
+use reqwest::Url;
+use rss::Channel;
+use serde::Deserialize;
+
+use crate::{
+    Extractor, ItemizerArg, ItemizerRes, PseudoCategory, PseudoEnclosure, PseudoGuid, PseudoItem,
+};
+
+/// Built-in extractor passing through the items of RSS 2.0, Atom and JSON Feed origins
+pub struct FeedExtractor;
+
+#[async_trait::async_trait]
+impl Extractor for FeedExtractor {
+    async fn extract(&self, arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+        let text = arg
+            .webstr
+            .as_deref()
+            .unwrap_or_default()
+            .trim_start_matches('\u{feff}')
+            .trim_start();
+        let start = text.chars().take(1024).collect::<String>();
+
+        let (items, continuation): (Vec<PseudoItem>, _) = if text.starts_with('{') {
+            let feed: JsonFeed = serde_json::from_str(text)?;
+            let items = feed.items.into_iter().map(PseudoItem::from).collect();
+            (items, feed.next_url)
+        } else if start.contains("<rss") {
+            let channel = Channel::read_from(text.as_bytes())?;
+            let items = channel.items.into_iter().map(PseudoItem::from).collect();
+            (items, None)
+        } else if start.contains("<feed") {
+            let feed: Feed = text.parse()?;
+            let items = feed.entries().iter().map(atom_item).collect();
+            let next = feed.links().iter().find(|link| link.rel() == "next");
+            (items, next.map(|link| link.href().to_string()))
+        } else {
+            return Err(format!("{} is not a RSS 2.0, Atom or JSON Feed document", arg.url).into());
+        };
+
+        let base = Url::parse(arg.base_url())?;
+        let resolve = |url: String| base.join(&url).map_or(url, String::from);
+        let items = items.into_iter().map(|item| PseudoItem {
+            link: item.link.map(resolve),
+            enclosure: item.enclosure.map(|enclosure| PseudoEnclosure {
+                url: resolve(enclosure.url),
+                ..enclosure
+            }),
+            ..item
+        });
+
+        let continuation = match continuation {
+            Some(next) => Some(base.join(&next)?.to_string()),
+            None => None,
+        };
+
+        Ok(arg.output(items, continuation))
+    }
+}
+
+fn atom_item(entry: &Entry) -> PseudoItem {
+    let authors = entry
+        .authors()
+        .iter()
+        .map(|author| author.name())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let categories = entry
+        .categories()
+        .iter()
+        .map(|category| PseudoCategory {
+            name: category.term().to_string(),
+            domain: category.scheme().map(str::to_string),
+        })
+        .collect::<Vec<_>>();
+
+    PseudoItem {
+        title: Some(entry.title().to_string()).filter(|title| !title.is_empty()),
+        link: entry
+            .links()
+            .iter()
+            .find(|link| link.rel() == "alternate")
+            .or(entry.links().first())
+            .map(|link| link.href().to_string()),
+        description: entry.summary().map(|summary| summary.to_string()),
+        author: Some(authors).filter(|authors| !authors.is_empty()),
+        categories: Some(categories).filter(|categories| !categories.is_empty()),
+        guid: Some(entry.id())
+            .filter(|id| !id.is_empty())
+            .map(|id| PseudoGuid {
+                value: id.to_string(),
+                permalink: false,
+            }),
+        pub_date: Some(entry.published().unwrap_or(entry.updated()).to_rfc2822()),
+        content: entry
+            .content()
+            .and_then(|content| content.value())
+            .map(str::to_string),
+        ..Default::default()
+    }
+}
+
+/// JSON Feed 1.0 and 1.1 document
+#[derive(Deserialize)]
+struct JsonFeed {
+    #[serde(default)]
+    items: Vec<JsonFeedItem>,
+    next_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonFeedItem {
+    id: Option<serde_json::Value>,
+    url: Option<String>,
+    external_url: Option<String>,
+    title: Option<String>,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    summary: Option<String>,
+    date_published: Option<String>,
+    date_modified: Option<String>,
+    /// JSON Feed 1.0
+    author: Option<JsonFeedAuthor>,
+    #[serde(default)]
+    authors: Vec<JsonFeedAuthor>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    attachments: Vec<JsonFeedAttachment>,
+}
+
+#[derive(Deserialize)]
+struct JsonFeedAuthor {
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonFeedAttachment {
+    url: String,
+    mime_type: String,
+    size_in_bytes: Option<u64>,
+}
+
+impl From<JsonFeedItem> for PseudoItem {
+    fn from(val: JsonFeedItem) -> Self {
+        let authors = val
+            .authors
+            .into_iter()
+            .chain(val.author)
+            .filter_map(|author| author.name)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut item = PseudoItem {
+            title: val.title,
+            link: val.url.or(val.external_url),
+            description: val.summary,
+            author: Some(authors).filter(|authors| !authors.is_empty()),
+            categories: Some(val.tags).filter(|tags| !tags.is_empty()).map(|tags| {
+                tags.into_iter()
+                    .map(|name| PseudoCategory { name, domain: None })
+                    .collect()
+            }),
+            enclosure: val
+                .attachments
+                .into_iter()
+                .next()
+                .map(|attachment| PseudoEnclosure {
+                    url: attachment.url,
+                    length: attachment.size_in_bytes.unwrap_or_default().to_string(),
+                    mime_type: attachment.mime_type,
+                }),
+            guid: val.id.map(|id| PseudoGuid {
+                value: match id {
+                    serde_json::Value::String(id) => id,
+                    id => id.to_string(),
+                },
+                permalink: false,
+            }),
+            content: val.content_html.or(val.content_text),
+            ..Default::default()
+        };
+
+        if let Some(date) = val.date_published.or(val.date_modified) {
+            item.set_date(&date);
+        }
+
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeedExtractor;
+    use crate::{Extractor, FeedOption, ItemizerArg, ItemizerRes};
+
+    async fn extract(webstr: &str) -> ItemizerRes {
+        let arg = ItemizerArg::page(
+            "https://example.com/blog/feed",
+            webstr,
+            FeedOption::default(),
+        );
+        FeedExtractor.extract(&arg).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn atom_entries_are_mapped() {
+        let res = extract(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <feed xmlns="http://www.w3.org/2005/Atom">
+                <title>Blog</title>
+                <id>urn:blog</id>
+                <updated>2024-05-02T00:00:00Z</updated>
+                <link rel="next" href="feed?page=2"/>
+                <entry>
+                    <title>First</title>
+                    <id>urn:first</id>
+                    <link rel="edit" href="/edit/1"/>
+                    <link rel="alternate" href="posts/1"/>
+                    <author><name>A</name></author>
+                    <author><name>B</name></author>
+                    <category term="news" scheme="https://example.com/tags"/>
+                    <updated>2024-05-02T00:00:00Z</updated>
+                    <published>2024-05-01T10:00:00Z</published>
+                    <summary>Summary</summary>
+                    <content type="html">&lt;p&gt;Content&lt;/p&gt;</content>
+                </entry>
+            </feed>"#,
+        )
+        .await;
+
+        let item = &res.items[0];
+        assert_eq!(item.title.as_deref(), Some("First"));
+        assert_eq!(
+            item.link.as_deref(),
+            Some("https://example.com/blog/posts/1")
+        );
+        assert_eq!(item.author.as_deref(), Some("A, B"));
+        let categories = item.categories.as_ref().unwrap();
+        assert_eq!(categories[0].name, "news");
+        assert_eq!(
+            categories[0].domain.as_deref(),
+            Some("https://example.com/tags")
+        );
+        assert_eq!(item.guid.as_ref().unwrap().value, "urn:first");
+        assert_eq!(
+            item.pub_date.as_deref(),
+            Some("Wed, 1 May 2024 10:00:00 +0000")
+        );
+        assert_eq!(item.description.as_deref(), Some("Summary"));
+        assert_eq!(item.content.as_deref(), Some("<p>Content</p>"));
+
+        assert_eq!(
+            res.continuation.as_deref(),
+            Some("https://example.com/blog/feed?page=2")
+        );
+    }
+
+    #[tokio::test]
+    async fn json_feed_items_are_mapped() {
+        let res = extract(
+            r#"{
+                "version": "https://jsonfeed.org/version/1.1",
+                "title": "Blog",
+                "next_url": "/feed.json?page=2",
+                "items": [
+                    {
+                        "id": 1,
+                        "url": "posts/1",
+                        "title": "First",
+                        "content_text": "Text",
+                        "date_published": "2024-05-01T10:00:00Z",
+                        "authors": [{ "name": "A" }],
+                        "tags": ["news", "rust"],
+                        "attachments": [{ "url": "/1.mp3", "mime_type": "audio/mpeg", "size_in_bytes": 10 }]
+                    },
+                    { "id": "2", "external_url": "https://other.example/2", "author": { "name": "B" } }
+                ]
+            }"#,
+        )
+        .await;
+
+        let first = &res.items[0];
+        assert_eq!(first.title.as_deref(), Some("First"));
+        assert_eq!(
+            first.link.as_deref(),
+            Some("https://example.com/blog/posts/1")
+        );
+        assert_eq!(first.guid.as_ref().unwrap().value, "1");
+        assert_eq!(first.author.as_deref(), Some("A"));
+        assert_eq!(first.content.as_deref(), Some("Text"));
+        assert_eq!(
+            first.pub_date.as_deref(),
+            Some("Wed, 1 May 2024 10:00:00 +0000")
+        );
+        let tags: Vec<_> = first
+            .categories
+            .iter()
+            .flatten()
+            .map(|category| category.name.as_str())
+            .collect();
+        assert_eq!(tags, ["news", "rust"]);
+        let enclosure = first.enclosure.as_ref().unwrap();
+        assert_eq!(enclosure.url, "https://example.com/1.mp3");
+        assert_eq!(enclosure.length, "10");
+
+        let second = &res.items[1];
+        assert_eq!(second.link.as_deref(), Some("https://other.example/2"));
+        assert_eq!(second.guid.as_ref().unwrap().value, "2");
+        assert_eq!(second.author.as_deref(), Some("B"));
+
+        assert_eq!(
+            res.continuation.as_deref(),
+            Some("https://example.com/feed.json?page=2")
+        );
+    }
+
+    #[tokio::test]
+    async fn rss_items_are_passed_through() {
+        let res = extract(
+            r#"<?xml version="1.0"?>
+            <rss version="2.0"><channel>
+                <title>Blog</title><link>https://example.com/</link><description>d</description>
+                <item><title>First</title><link>/posts/1</link></item>
+            </channel></rss>"#,
+        )
+        .await;
+
+        assert_eq!(res.items[0].title.as_deref(), Some("First"));
+        assert_eq!(
+            res.items[0].link.as_deref(),
+            Some("https://example.com/posts/1")
+        );
+        assert_eq!(res.continuation, None);
+    }
+
+    #[tokio::test]
+    async fn other_documents_fail() {
+        let arg = ItemizerArg::page(
+            "https://example.com/",
+            "<html></html>",
+            FeedOption::default(),
+        );
+        assert!(FeedExtractor.extract(&arg).await.is_err());
+    }
+}
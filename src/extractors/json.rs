@@ -0,0 +1,184 @@
+use std::error::Error;
+
+use jsonpath_rust::JsonPath;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::Url;
+use serde_json::Value;
+
+use crate::{errors::sendable, Extractor, ItemizerArg, ItemizerRes, PseudoItem};
+
+/// Characters of a cursor escaped in the next url, all but the unreserved characters of RFC 3986
+const CURSOR: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Built-in extractor mapping JSONPaths onto item fields, configured by feed.json
+pub struct JsonExtractor;
+
+#[async_trait::async_trait]
+impl Extractor for JsonExtractor {
+    async fn extract(&self, arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+        let arg = arg.clone();
+        // large pages are parsed off the async runtime, so the script timeout can still fire
+        tokio::task::spawn_blocking(move || extract(&arg).map_err(sendable))
+            .await?
+            .map_err(|e| e as Box<dyn Error>)
+    }
+}
+
+/// Items matching the JSONPaths of feed.json
+fn extract(arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+    let Some(option) = &arg.feed.json else {
+        return Err(crate::Error::MissingOption("json".to_string()).into());
+    };
+    let body: Value = serde_json::from_str(arg.webstr.as_deref().unwrap_or_default())?;
+    let base = Url::parse(arg.base_url())?;
+
+    let mut items = Vec::new();
+    for value in body.query(&option.items)? {
+        items.extend(PseudoItem::from_fields(&base, |name| {
+            match option.fields.get(name) {
+                Some(path) => values(path, value),
+                None => Ok(Vec::new()),
+            }
+        })?);
+    }
+
+    let continuation = match &option.next {
+        Some(path) => match values(path, &body)?.into_iter().next() {
+            Some(next) => {
+                let url = match &option.next_url {
+                    Some(template) => {
+                        template.replace("{next}", &utf8_percent_encode(&next, CURSOR).to_string())
+                    }
+                    None => next,
+                };
+                Some(base.join(&url)?.to_string())
+            }
+            None => None,
+        },
+        None => None,
+    };
+
+    Ok(arg.output(items, continuation))
+}
+
+/// Values matched by a path as text, one for each match that is not null
+fn values(path: &str, value: &Value) -> Result<Vec<String>, Box<dyn Error>> {
+    Ok(value
+        .query(path)?
+        .into_iter()
+        .filter_map(|value| match value {
+            Value::Null => None,
+            Value::String(value) => Some(value.trim().to_string()),
+            value => Some(value.to_string()),
+        })
+        .filter(|value| !value.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract;
+    use crate::{FeedOption, ItemFields, ItemizerArg, JsonOption};
+
+    #[test]
+    fn paths_are_mapped_from_each_item() {
+        let path = |path: &str| Some(path.to_string());
+        let feed = FeedOption {
+            json: Some(JsonOption {
+                items: "$.data[*]".to_string(),
+                next: path("$.links.next"),
+                fields: ItemFields {
+                    title: path("$.attributes.title"),
+                    link: path("$.attributes.slug"),
+                    categories: path("$.attributes.tags[*]"),
+                    pub_date: path("$.attributes.created"),
+                    author: path("$.attributes.author"),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let arg = ItemizerArg::page(
+            "https://example.com/api/posts",
+            r#"{
+                "data": [
+                    {
+                        "attributes": {
+                            "title": " First ",
+                            "slug": "posts/1",
+                            "tags": ["news", 2],
+                            "created": 1714557600,
+                            "author": null
+                        }
+                    },
+                    { "attributes": { "slug": "/posts/2", "created": "2024-05-02T00:00:00Z" } },
+                    { "attributes": { "tags": ["orphan"] } }
+                ],
+                "links": { "next": "/api/posts?page=2" }
+            }"#,
+            feed,
+        );
+        let res = extract(&arg).unwrap();
+
+        assert_eq!(res.items.len(), 2);
+        let first = &res.items[0];
+        assert_eq!(first.title.as_deref(), Some("First"));
+        assert_eq!(
+            first.link.as_deref(),
+            Some("https://example.com/api/posts/1")
+        );
+        let categories: Vec<_> = first
+            .categories
+            .iter()
+            .flatten()
+            .map(|category| category.name.as_str())
+            .collect();
+        assert_eq!(categories, ["news", "2"]);
+        assert_eq!(
+            first.pub_date.as_deref(),
+            Some("Wed, 1 May 2024 10:00:00 +0000")
+        );
+        assert_eq!(first.author, None);
+
+        let second = &res.items[1];
+        assert_eq!(second.title, None);
+        assert_eq!(second.link.as_deref(), Some("https://example.com/posts/2"));
+        assert_eq!(
+            second.pub_date.as_deref(),
+            Some("Thu, 2 May 2024 00:00:00 +0000")
+        );
+
+        assert_eq!(
+            res.continuation.as_deref(),
+            Some("https://example.com/api/posts?page=2")
+        );
+    }
+
+    #[test]
+    fn cursors_are_percent_encoded_into_the_next_url() {
+        let feed = FeedOption {
+            json: Some(JsonOption {
+                items: "$.posts[*]".to_string(),
+                next: Some("$.cursor".to_string()),
+                next_url: Some("/api/posts?cursor={next}&limit=2".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let arg = ItemizerArg::page(
+            "https://example.com/api/posts",
+            r#"{"posts": [], "cursor": "ab+c/d=="}"#,
+            feed,
+        );
+
+        assert_eq!(
+            extract(&arg).unwrap().continuation.as_deref(),
+            Some("https://example.com/api/posts?cursor=ab%2Bc%2Fd%3D%3D&limit=2")
+        );
+    }
+}
@@ -0,0 +1,16 @@
+mod css;
+pub use css::*;
+mod feed;
+pub use feed::*;
+mod json;
+pub use json::*;
+mod regex;
+pub use regex::*;
+mod registry;
+pub use registry::*;
+mod rhai;
+pub use rhai::*;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
@@ -0,0 +1,145 @@
+use std::error::Error;
+
+use regex::{Captures, Regex};
+use reqwest::Url;
+
+use crate::{errors::sendable, Extractor, ItemizerArg, ItemizerRes, PseudoItem};
+
+/// Built-in extractor turning each match of a regex into an item, configured by feed.regex
+pub struct RegexExtractor;
+
+#[async_trait::async_trait]
+impl Extractor for RegexExtractor {
+    async fn extract(&self, arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+        let arg = arg.clone();
+        // large pages are parsed off the async runtime, so the script timeout can still fire
+        tokio::task::spawn_blocking(move || extract(&arg).map_err(sendable))
+            .await?
+            .map_err(|e| e as Box<dyn Error>)
+    }
+}
+
+/// Items from each match of the regex of feed.regex
+fn extract(arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+    let Some(option) = &arg.feed.regex else {
+        return Err(crate::Error::MissingOption("regex".to_string()).into());
+    };
+    let regex = Regex::new(&option.pattern)?;
+    let base = Url::parse(arg.base_url())?;
+
+    let mut items = Vec::new();
+    for captures in regex.captures_iter(arg.webstr.as_deref().unwrap_or_default()) {
+        items.extend(PseudoItem::from_fields(&base, |name| {
+            let value = match option.fields.get(name) {
+                Some(template) => expand(template, &captures),
+                None => captures
+                    .name(name)
+                    .map(|value| value.as_str().to_string())
+                    .unwrap_or_default(),
+            };
+
+            Ok(Some(value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .into_iter()
+                .collect())
+        })?);
+    }
+
+    Ok(arg.output(items, None))
+}
+
+/// Replace each `{name}` in template with the named capture, or nothing if it did not match
+fn expand(template: &str, captures: &Captures) -> String {
+    let mut expanded = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+
+        expanded.push_str(&rest[..start]);
+        let name = &rest[start + 1..start + end];
+        expanded.push_str(captures.name(name).map_or("", |value| value.as_str()));
+        rest = &rest[start + end + 1..];
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use super::{expand, extract};
+    use crate::{FeedOption, ItemFields, ItemizerArg, RegexOption};
+
+    #[test]
+    fn templates_expand_named_captures() {
+        let regex = Regex::new(r"(?P<version>v[\d.]+)(?: \((?P<date>[\d-]+)\))?").unwrap();
+        let captures = regex.captures("v1.2.0").unwrap();
+
+        assert_eq!(expand("Release {version}", &captures), "Release v1.2.0");
+        assert_eq!(expand("{date}T00:00:00Z", &captures), "T00:00:00Z");
+        assert_eq!(expand("{unknown}{version}", &captures), "v1.2.0");
+        assert_eq!(expand("{version} {unclosed", &captures), "v1.2.0 {unclosed");
+    }
+
+    #[test]
+    fn matches_become_items() {
+        let feed = FeedOption {
+            regex: Some(RegexOption {
+                pattern: r"(?m)^## (?P<title>\S+) \((?P<date>[\d-]+)\)$".to_string(),
+                fields: ItemFields {
+                    title: Some("Release {title}".to_string()),
+                    link: Some("#{title}".to_string()),
+                    pub_date: Some("{date}T00:00:00Z".to_string()),
+                    ..Default::default()
+                },
+            }),
+            ..Default::default()
+        };
+        let arg = ItemizerArg::page(
+            "https://example.com/CHANGELOG.md",
+            "# Changelog\n\n## v1.2.0 (2024-05-02)\n\n- fix\n\n## v1.1.0 (2024-05-01)\n",
+            feed,
+        );
+        let res = extract(&arg).unwrap();
+
+        let titles: Vec<_> = res.items.iter().map(|item| item.title.as_deref()).collect();
+        assert_eq!(titles, [Some("Release v1.2.0"), Some("Release v1.1.0")]);
+        assert_eq!(
+            res.items[0].link.as_deref(),
+            Some("https://example.com/CHANGELOG.md#v1.2.0")
+        );
+        assert_eq!(
+            res.items[1].pub_date.as_deref(),
+            Some("Wed, 1 May 2024 00:00:00 +0000")
+        );
+        assert_eq!(res.continuation, None);
+    }
+
+    #[test]
+    fn fields_without_a_template_take_the_group_of_the_same_name() {
+        let feed = FeedOption {
+            regex: Some(RegexOption {
+                pattern: r#"<a href="(?P<link>[^"]+)">(?P<title>[^<]*)</a>"#.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let arg = ItemizerArg::page(
+            "https://example.com/list",
+            r#"<a href="/1"> One </a><a href="/2"></a>"#,
+            feed,
+        );
+        let res = extract(&arg).unwrap();
+
+        assert_eq!(res.items.len(), 2);
+        assert_eq!(res.items[0].title.as_deref(), Some("One"));
+        assert_eq!(res.items[0].link.as_deref(), Some("https://example.com/1"));
+        assert_eq!(res.items[1].title, None);
+        assert_eq!(res.items[1].link.as_deref(), Some("https://example.com/2"));
+    }
+}
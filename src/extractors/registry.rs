@@ -0,0 +1,55 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use crate::Extractor;
+
+use super::{CssExtractor, FeedExtractor, JsonExtractor, RegexExtractor, RhaiExtractor};
+
+/// Extractors that feeds can reference by name, starting with the built-in extractors
+pub struct Extractors(OnceLock<RwLock<HashMap<String, Arc<dyn Extractor>>>>);
+
+impl Extractors {
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Register an extractor under a name, replacing any extractor with the same name
+    pub fn register(&self, name: impl Into<String>, extractor: impl Extractor + 'static) {
+        self.0
+            .get_or_init(Self::builtins)
+            .write()
+            .unwrap()
+            .insert(name.into(), Arc::new(extractor));
+    }
+
+    /// Get the extractor registered under a name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Extractor>> {
+        self.0
+            .get_or_init(Self::builtins)
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+    }
+
+    /// Built-in extractors under their names
+    fn builtins() -> RwLock<HashMap<String, Arc<dyn Extractor>>> {
+        let mut extractors: HashMap<String, Arc<dyn Extractor>> = HashMap::new();
+        extractors.insert("css".to_string(), Arc::new(CssExtractor));
+        extractors.insert("feed".to_string(), Arc::new(FeedExtractor));
+        extractors.insert("json".to_string(), Arc::new(JsonExtractor));
+        extractors.insert("regex".to_string(), Arc::new(RegexExtractor));
+        extractors.insert("rhai".to_string(), Arc::new(RhaiExtractor));
+        #[cfg(feature = "wasm")]
+        extractors.insert("wasm".to_string(), Arc::new(super::WasmExtractor));
+        RwLock::new(extractors)
+    }
+}
+
+impl Default for Extractors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    error::Error,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use ego_tree::NodeId;
+use reqwest::Url;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope};
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{Extractor, ItemizerArg, ItemizerRes, PseudoItem, RhaiOption, MASTER};
+
+/// Built-in extractor running a Rhai script, configured by feed.rhai
+pub struct RhaiExtractor;
+
+#[async_trait::async_trait]
+impl Extractor for RhaiExtractor {
+    async fn extract(&self, arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+        let Some(option) = arg.feed.rhai.clone() else {
+            return Err(crate::Error::MissingOption("rhai".to_string()).into());
+        };
+        let source = match (&option.file, &option.script) {
+            (Some(file), _) => tokio::fs::read_to_string(file).await?,
+            (None, Some(script)) => script.clone(),
+            (None, None) => return Err(crate::Error::MissingOption("rhai.file".to_string()).into()),
+        };
+
+        let url = arg.url.clone();
+        let webstr = arg.webstr.clone();
+        let preexists = arg.preexists.clone();
+        let deadline = Instant::now() + Duration::from_secs(MASTER.get().unwrap().script_timeout);
+
+        // scripts run until they return, exceed the operations limit or time out, off the async runtime
+        let mut res = tokio::task::spawn_blocking(move || {
+            run(&option, &source, url, webstr, preexists, deadline).map_err(|e| e.to_string())
+        })
+        .await??;
+
+        let base = Url::parse(arg.base_url())?;
+        for item in res.items.iter_mut() {
+            if let Some(link) = &item.link {
+                item.link = Some(base.join(link)?.to_string());
+            }
+
+            if let Some(date) = item.pub_date.take() {
+                item.set_date(&date);
+            }
+        }
+
+        if let Some(continuation) = &res.continuation {
+            res.continuation = Some(base.join(continuation)?.to_string());
+        }
+
+        // scripts may leave out known items themselves
+        let mut output = arg.output(res.items, res.continuation);
+        output.known += res.known;
+        Ok(output)
+    }
+}
+
+/// Evaluate the script, which returns either an array of items or an object like the extractor script output
+///
+/// The script is terminated at the deadline, as the thread would otherwise run on after the fetch failed
+fn run(
+    option: &RhaiOption,
+    source: &str,
+    url: String,
+    webstr: Option<String>,
+    preexists: Vec<PseudoItem>,
+    deadline: Instant,
+) -> Result<ItemizerRes, Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    engine.set_max_operations(option.max_operations);
+    engine.on_progress(move |operations| {
+        // checking the clock every operation would slow scripts down
+        if operations % 1024 == 0 && Instant::now() >= deadline {
+            return Some("script timed out".into());
+        }
+
+        None
+    });
+    engine
+        .register_type_with_name::<Element>("Element")
+        .register_fn("parse_html", Element::parse)
+        .register_fn("select", Element::select)
+        .register_fn("select_first", Element::select_first)
+        .register_fn("attr", Element::attr)
+        .register_get("text", Element::text)
+        .register_get("html", Element::html);
+
+    let mut scope = Scope::new();
+    scope.push_constant("url", url);
+    scope.push_constant("webstr", webstr.map_or(Dynamic::UNIT, Dynamic::from));
+    scope.push_constant("preexists", rhai::serde::to_dynamic(preexists)?);
+
+    let output: Dynamic = engine.eval_with_scope(&mut scope, source)?;
+
+    if output.is_array() {
+        Ok(ItemizerRes {
+            continuation: None,
+            items: rhai::serde::from_dynamic::<Vec<PseudoItem>>(&output)?,
+            known: 0,
+        })
+    } else {
+        rhai::serde::from_dynamic(&output)
+    }
+}
+
+/// Element of a parsed html document, exposed to scripts
+#[derive(Clone)]
+struct Element {
+    document: Rc<Html>,
+    id: NodeId,
+}
+
+impl Element {
+    /// Root element of a html document
+    fn parse(html: &str) -> Self {
+        let document = Html::parse_document(html);
+        let id = document.root_element().id();
+
+        Self {
+            document: Rc::new(document),
+            id,
+        }
+    }
+
+    fn element(&self) -> ElementRef<'_> {
+        ElementRef::wrap(self.document.tree.get(self.id).unwrap()).unwrap()
+    }
+
+    /// Descendants matching a CSS selector
+    fn select(&mut self, selector: &str) -> Result<Array, Box<EvalAltResult>> {
+        let selector =
+            Selector::parse(selector).map_err(|e| format!("invalid selector {selector}: {e}"))?;
+
+        Ok(self
+            .element()
+            .select(&selector)
+            .map(|element| {
+                Dynamic::from(Self {
+                    document: self.document.clone(),
+                    id: element.id(),
+                })
+            })
+            .collect())
+    }
+
+    /// First descendant matching a CSS selector, () if none
+    fn select_first(&mut self, selector: &str) -> Result<Dynamic, Box<EvalAltResult>> {
+        Ok(self
+            .select(selector)?
+            .into_iter()
+            .next()
+            .unwrap_or(Dynamic::UNIT))
+    }
+
+    /// Value of an attribute, () if missing
+    fn attr(&mut self, name: &str) -> Dynamic {
+        self.element()
+            .value()
+            .attr(name)
+            .map_or(Dynamic::UNIT, |value| value.into())
+    }
+
+    /// Text content with whitespace collapsed
+    fn text(&mut self) -> String {
+        self.element()
+            .text()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn html(&mut self) -> String {
+        self.element().inner_html()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use rhai::EvalAltResult;
+
+    use super::run;
+    use crate::RhaiOption;
+
+    #[test]
+    fn endless_scripts_stop_at_the_deadline() {
+        // no operations limit, so only the deadline can stop the script
+        let option = RhaiOption {
+            max_operations: 0,
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let res = run(
+            &option,
+            "loop {}",
+            "https://example.com/".to_string(),
+            None,
+            Vec::new(),
+            start + Duration::from_millis(100),
+        );
+
+        assert!(matches!(
+            res.err().as_deref(),
+            Some(EvalAltResult::ErrorTerminated(..))
+        ));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn arrays_are_items() {
+        let res = run(
+            &RhaiOption::default(),
+            r#"[#{ title: url }, #{ title: "b", link: "/b" }]"#,
+            "https://example.com/".to_string(),
+            None,
+            Vec::new(),
+            Instant::now() + Duration::from_secs(5),
+        )
+        .unwrap();
+
+        let titles: Vec<_> = res.items.iter().map(|item| item.title.as_deref()).collect();
+        assert_eq!(titles, [Some("https://example.com/"), Some("b")]);
+        assert_eq!(res.continuation, None);
+    }
+}
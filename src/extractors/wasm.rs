@@ -0,0 +1,140 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::{Duration, SystemTime},
+};
+
+use wasmtime::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+use wasmtime_wasi::{
+    pipe::{MemoryInputPipe, MemoryOutputPipe},
+    preview1::{self, WasiP1Ctx},
+    I32Exit, WasiCtxBuilder,
+};
+
+use crate::{Extractor, ItemizerArg, ItemizerRes, WasmOption, MASTER};
+
+/// Interval between epochs of the engine, the granularity of the script timeout
+const EPOCH_TICK: Duration = Duration::from_millis(100);
+
+/// Number of bytes of stderr kept for the log, writing more fails the run
+const MAX_STDERR: usize = 64 * 1024;
+
+/// Built-in extractor running a sandboxed WASI module, configured by feed.wasm
+///
+/// The module gets no files, network, environment or arguments, only the input on stdin
+pub struct WasmExtractor;
+
+#[async_trait::async_trait]
+impl Extractor for WasmExtractor {
+    async fn extract(&self, arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+        let Some(option) = arg.feed.wasm.clone() else {
+            return Err(crate::Error::MissingOption("wasm".to_string()).into());
+        };
+        let input = serde_json::to_vec(arg)?;
+
+        // modules run until they exit, run out of fuel or time out, off the async runtime
+        let output = tokio::task::spawn_blocking(move || run(&option, input))
+            .await?
+            .map_err(|e| format!("{e:#}"))?;
+
+        match serde_json::from_slice(&output) {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                println!("Could not deserialize module output: {e}");
+                println!("Module stdout:\n{}", String::from_utf8_lossy(&output));
+                Err(e.into())
+            }
+        }
+    }
+}
+
+/// Store data of a run
+struct State {
+    wasi: WasiP1Ctx,
+    limits: StoreLimits,
+}
+
+/// Run the module of option with input on stdin, returning stdout
+fn run(option: &WasmOption, input: Vec<u8>) -> wasmtime::Result<Vec<u8>> {
+    let engine = engine();
+    let module = module(engine, Path::new(&option.module))?;
+
+    let stdout = MemoryOutputPipe::new(option.max_memory);
+    let stderr = MemoryOutputPipe::new(MAX_STDERR);
+    let wasi = WasiCtxBuilder::new()
+        .stdin(MemoryInputPipe::new(input))
+        .stdout(stdout.clone())
+        .stderr(stderr.clone())
+        .build_p1();
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(option.max_memory)
+        .instances(1)
+        .build();
+
+    let mut store = Store::new(engine, State { wasi, limits });
+    store.limiter(|state| &mut state.limits);
+    store.set_fuel(option.fuel)?;
+    // traps a tick after the script timeout, as the thread would otherwise run on after the fetch failed
+    let timeout = Duration::from_secs(MASTER.get().unwrap().script_timeout);
+    store.set_epoch_deadline((timeout.as_millis() / EPOCH_TICK.as_millis()) as u64 + 1);
+
+    let mut linker = Linker::new(engine);
+    preview1::add_to_linker_sync(&mut linker, |state: &mut State| &mut state.wasi)?;
+    let start = linker
+        .instantiate(&mut store, &module)?
+        .get_typed_func::<(), ()>(&mut store, "_start")?;
+
+    let res = start.call(&mut store, ());
+    drop(store);
+
+    let stderr = stderr.contents();
+    if !stderr.is_empty() {
+        println!("Module stderr:\n{}", String::from_utf8_lossy(&stderr));
+    }
+
+    if let Err(e) = res {
+        // proc_exit traps even when exiting successfully
+        if !matches!(e.downcast_ref::<I32Exit>(), Some(I32Exit(0))) {
+            return Err(e);
+        }
+    }
+
+    Ok(stdout.contents().to_vec())
+}
+
+/// Engine shared by all modules, with fuel metering and an epoch every EPOCH_TICK
+fn engine() -> &'static Engine {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    ENGINE.get_or_init(|| {
+        let engine =
+            Engine::new(Config::new().consume_fuel(true).epoch_interruption(true)).unwrap();
+
+        let ticker = engine.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(EPOCH_TICK);
+            ticker.increment_epoch();
+        });
+
+        engine
+    })
+}
+
+/// Compiled module at path, compiled again when the file changes
+fn module(engine: &Engine, path: &Path) -> wasmtime::Result<Module> {
+    static MODULES: OnceLock<Mutex<HashMap<PathBuf, (SystemTime, Module)>>> = OnceLock::new();
+
+    let modified = path.metadata()?.modified()?;
+    let mut modules = MODULES.get_or_init(Default::default).lock().unwrap();
+
+    if let Some((compiled, module)) = modules.get(path) {
+        if *compiled == modified {
+            return Ok(module.clone());
+        }
+    }
+
+    let module = Module::from_file(engine, path)?;
+    modules.insert(path.to_path_buf(), (modified, module.clone()));
+    Ok(module)
+}
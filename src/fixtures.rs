@@ -0,0 +1,85 @@
+use std::{error::Error, path::Path};
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::{response::RawResponse, traits::Saveable};
+
+/// Recorded response, the body is saved in a file next to it
+#[derive(Serialize, Deserialize)]
+pub struct Fixture {
+    /// Requested URL
+    pub url: String,
+    /// URL after following redirects
+    #[serde(rename = "final-url")]
+    pub final_url: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+}
+
+impl Saveable for Fixture {}
+
+impl Fixture {
+    /// Save a response to url in dir
+    pub async fn record(dir: &Path, url: &str, res: &RawResponse) -> Result<(), Box<dyn Error>> {
+        let name = Self::name(url);
+        let fixture = Self {
+            url: url.to_string(),
+            final_url: res.url.clone(),
+            status: res.status.as_u16(),
+            headers: res
+                .headers
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        key.to_string(),
+                        String::from_utf8_lossy(value.as_bytes()).to_string(),
+                    )
+                })
+                .collect(),
+        };
+
+        fixture
+            .save_json_pretty(&dir.join(format!("{name}.json")))
+            .await?;
+        fs::write(dir.join(format!("{name}.body")), &res.body).await?;
+        Ok(())
+    }
+
+    /// Load the saved response to url from dir
+    pub async fn replay(dir: &Path, url: &str) -> Result<RawResponse, Box<dyn Error>> {
+        let name = Self::name(url);
+        let path = dir.join(format!("{name}.json"));
+        if !fs::try_exists(&path).await? {
+            return Err(crate::Error::MissingFixture(url.to_string()).into());
+        }
+
+        let fixture = Self::load_json(&path).await?;
+        let mut headers = HeaderMap::new();
+        for (key, value) in fixture.headers {
+            headers.append(
+                HeaderName::from_bytes(key.as_bytes())?,
+                HeaderValue::from_str(&value)?,
+            );
+        }
+
+        Ok(RawResponse {
+            url: fixture.final_url,
+            status: StatusCode::from_u16(fixture.status)?,
+            headers,
+            body: fs::read(dir.join(format!("{name}.body"))).await?,
+        })
+    }
+
+    /// File name of the fixture, a FNV-1a hash of the url so it is stable across builds
+    fn name(url: &str) -> String {
+        let hash = url.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        format!("{hash:016x}")
+    }
+}
@@ -4,7 +4,7 @@
 //!
 //! Get started by creating an event loop.
 //!
-//! ```
+//! ```ignore
 //! #[tokio::main]
 //! async fn main() {
 //!     // initialise values
@@ -39,7 +39,7 @@
 //!
 //! To change the config directory location, specify the path:
 //!
-//! ```
+//! ```ignore
 //! let config_path = PathBuf::from("/my/special/path");
 //! scrapyard::init(Some(config_path)).await;
 //! ```
@@ -50,8 +50,34 @@
 //! {
 //!     "store": String, // i.e. /home/user/.local/share/scrapyard/
 //!     "max-retries": Number, // number of retries before giving up
+//!     "retry": { // delays between retries
+//!         "base-delay": Number, // milliseconds before the first retry, doubled on each retry
+//!         "max-delay": Number, // maximum milliseconds between retries, a longer Retry-After stops retrying
+//!         "jitter": Boolean // randomise delays between half and the full delay
+//!     },
 //!     "request-timeout": Number, // number of seconds before giving up request
 //!     "script-timeout": Number, // number of seconds before giving up on the extractor script
+//!     "max-body-size": Number, // maximum number of bytes in a response, larger responses fail the fetch
+//!     "http": { // options for the shared http client
+//!         "proxy": String?, // proxy url for all requests, i.e. http://proxy.local:3128
+//!         "no-proxy": String?, // comma separated hosts to not proxy
+//!         "ca-certs": [String], // paths to additional PEM root certificates
+//!         "accept-invalid-certs": Boolean, // skip TLS certificate validation
+//!         "max-redirects": Number, // maximum number of redirects to follow
+//!         "gzip": Boolean, // accept gzip compressed responses
+//!         "brotli": Boolean, // accept brotli compressed responses
+//!         "deflate": Boolean, // accept deflate compressed responses
+//!         "user-agent": String? // default User-Agent header
+//!     },
+//!     "rate-limit": { // politeness towards each host, shared by all feeds
+//!         "min-delay": Number, // minimum number of milliseconds between requests to a host
+//!         "max-connections": Number // maximum number of concurrent requests to a host
+//!     },
+//!     "robots": { // robots.txt compliance
+//!         "enabled": Boolean, // check robots.txt before each request, disallowed urls fail the fetch
+//!         "user-agent": String, // product token to match in robots.txt
+//!         "max-age": Number // number of seconds before a cached robots.txt is fetched again
+//!     }
 //! }
 //! ```
 //!
@@ -61,7 +87,9 @@
 //!
 //! ```json
 //! {
-//!     "origin": String, // origin of the feed
+//!     "origin": String, // origin of the feed, either a url or a file:// path, directory or glob (i.e. file:///reports/*.html), the fetch fails if no files match or if pages of other origins link to files
+//!     "origins": [String], // additional origins, items from all origins are merged into one feed
+//!     "values": [String], // each origin containing "{value}" is fetched once per value, i.e. https://example.com/tag/{value}
 //!     "label": String, // text id of the feed
 //!     "max-length": Number, // maximum number of items allowed in the feed
 //!     "fetch-length": Number, // maximum number of items allowed to be fetched each interval
@@ -69,11 +97,60 @@
 //!     "idle-limit": Number, // number of seconds without requests to that feed before fetching stops
 //!     "sort": Boolean, // to sort by publish date or not
 //!     "extractor": [String], // all command line args to run the extractor, i.e. ["node", "extractor.js"]
+//!     "extractor-name": String?, // name of a registered in process extractor, used instead of "extractor"
+//!     "css": Object?, // options for the built-in "css" extractor, see built-in extractors below
+//!     "json": Object?, // options for the built-in "json" extractor
+//!     "regex": Object?, // options for the built-in "regex" extractor
+//!     "rhai": Object?, // options for the built-in "rhai" extractor
+//!     "wasm": Object?, // options for the built-in "wasm" extractor
+//!     "worker": Boolean, // keep the extractor running and send pages over stdin, see worker mode below
 //!
 //!     "title": String, // displayed feed title
 //!     "link": String, // displayed feed source url
 //!     "description": String, // displayed feed description
-//!     "fetch": Boolean // should the crate fetch the content, or let the script do it
+//!     "fetch": Boolean, // should the crate fetch the content, or let the script do it
+//!     "pagination": { // find pages without the extractor, "continuation" is ignored when set
+//!         "type": "page" | "offset" | "next", // increment a page number, increment an offset, or follow a link
+//!         "param": String, // query parameter of the page number or offset
+//!         "start": Number?, // first page number or offset, defaults to 1 for pages and 0 for offsets
+//!         "limit": Number, // items per page, added to the offset for each page
+//!         "limit-param": String?, // query parameter to send the limit in
+//!         "selector": String, // CSS selector of the link to the next page, i.e. "a[rel=next]"
+//!         "concurrency": Number // number of pages to fetch at once, not for "next"
+//!     }?, // stops at the first page without items, or when "fetch-length" items are found
+//!     "max-pages": Number, // maximum number of pages to fetch from each origin, including continuations
+//!     "stop-at-known": Boolean, // stop following pages after one with no new items
+//!     "known-limit": Number?, // stop following pages once this many known items are found, counting "known" from the extractor
+//!     "binary": "text" | "base64" | "file", // how non-text responses (PDF, images...) are passed to the extractor
+//!     "max-body-size": Number?, // overrides "max-body-size" in scrapyard.json for this feed
+//!     "request": { // http request options, applied to the origin and all continuations
+//!         "method": String, // i.e. GET or POST
+//!         "headers": { String: String }, // additional request headers
+//!         "query": { String: String }, // query parameters appended to the url
+//!         "body": String?, // request body, "{url}" is replaced with the url being fetched
+//!         "user-agent": String?, // value of the User-Agent header
+//!         "conditional": Boolean, // send ETag/Last-Modified from the last fetch, unchanged pages are not extracted
+//!         "accept-errors": Boolean // pass non-2xx responses to the extractor instead of failing the fetch
+//!     },
+//!     "http": Object?, // same as "http" in scrapyard.json, replaces the global options for this feed
+//!     "rate-limit": Object?, // same as "rate-limit" in scrapyard.json, replaces the global options for this feed
+//!     "robots": Boolean?, // check robots.txt for this feed, overrides "robots.enabled" in scrapyard.json
+//!     "retry": Object?, // same as "retry" in scrapyard.json, replaces the global options for this feed
+//!     "cookies": { // cookie jar shared by the origin and all continuations
+//!         "persist": Boolean, // save cookies to store/<label>/cookies.json
+//!         "initial": [String] // cookies to set before the first request, i.e. ["consent=yes; Domain=example.com"]
+//!     }?,
+//!     "auth": { // authentication, only sent to the origin hosts, redirects to other hosts fail the fetch
+//!         "type": "basic" | "bearer" | "header" | "query",
+//!         "username": String, // for basic
+//!         "name": String, // header or query parameter name, for header and query
+//!         "password" | "token" | "value": { "env": String } | { "file": String } // secret from an environment variable or file
+//!     }?,
+//!     "expose-secret": Boolean, // pass the resolved secret to the extractor as "secret"
+//!     "fixtures": { // record responses to develop extractors offline
+//!         "mode": "off" | "record" | "replay", // save every response, or serve saved responses instead of the network
+//!         "dir": String? // directory of the recorded responses, defaults to store/<label>/fixtures
+//!     }
 //! }
 //! ```
 //!
@@ -100,7 +177,16 @@
 //! ```json
 //! {
 //!     "url": String, // origin of the info fetched
-//!     "webstr": String?, // response from the url, only if feed.fetch = true
+//!     "webstr": String?, // response from the url decoded as text, only if feed.fetch = true
+//!     "webbase64": String?, // base64 encoded non-text response, only if feed.binary = "base64"
+//!     "webfile": String?, // path to a file with the non-text response, only if feed.binary = "file"
+//!     "response": { // response details, only if feed.fetch = true
+//!         "url": String, // url after following redirects, use this to resolve relative links
+//!         "status": Number, // http status code
+//!         "headers": { String: String }, // response headers with lowercase names
+//!         "contentType": String? // mime type from the Content-Type header, or detected from the content
+//!     }?,
+//!     "secret": String?, // resolved auth secret, only if feed.expose-secret = true
 //!     "preexists": [ PseudoItem ], // don't output these again to avoid duplication
 //!     "lengthLeft": Number // maximum length before the fetch-length quota is met
 //!     
@@ -113,17 +199,170 @@
 //! ```json
 //! {
 //!     "items": [PseudoItem], // list of items extracted
-//!     "continuation": String? // optionally continue fetching in the next url
+//!     "continuation": String?, // optionally continue fetching in the next url, the fetch fails if it was already fetched
+//!     "known": Number? // number of items left out because they were in preexists, for feed.known-limit
+//! }
+//! ```
+//!
+//! #### Worker mode
+//!
+//! With `"worker": true` the extractor is started once without arguments and kept running, feeds with
+//! the same extractor command share one process. Each page is written to its stdin as 1 line of the
+//! JSON input above, and 1 line of the JSON output is expected back on stdout. Logs should go to stderr.
+//!
+//! Before each page the worker is sent `{"ping":true}` and must answer `{"pong":true}`. A worker that
+//! exited, timed out or failed the health check is restarted. The worker should exit when its stdin is closed.
+//!
+//! #### In process extractors
+//!
+//! Extractors can also be written in Rust by implementing [Extractor](https://docs.rs/scrapyard/latest/trait.Extractor.html),
+//! it gets the same input and returns the same output as an extractor script. Register it before
+//! fetching, and reference it from a feed with `"extractor-name"`.
+//!
+//! ```ignore
+//! struct Titles;
+//!
+//! #[async_trait::async_trait]
+//! impl Extractor for Titles {
+//!     async fn extract(&self, arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>> {
+//!         // parse arg.webstr
+//!     }
+//! }
+//!
+//! EXTRACTORS.register("titles", Titles);
+//! ```
+//!
+//! #### Built-in extractors
+//!
+//! These are registered by default, and configured by an option of the same name in the feed.
+//! Items of **css**, **json** and **regex** need a title or link. Relative links are resolved against the
+//! page url, every value of "category" is a category, and "pubDate" can also be an RFC 3339 date or a
+//! unix timestamp in seconds, which are converted to RFC 2822.
+//!
+//! **css** maps CSS selectors onto item fields, the next link is used as the continuation.
+//!
+//! ```json
+//! {
+//!     "extractor-name": "css",
+//!     "css": {
+//!         "item": String, // selector of each item, i.e. ".post"
+//!         "next": String?, // selector of the link to the next page, i.e. "a[rel=next]"
+//!         "title" | "link" | "description" | "author" | "category" | "comments" | "guid" | "pubDate" | "content": {
+//!             "selector": String?, // selector within the item, the item itself if unset
+//!             "attr": String?, // attribute to take the value from, i.e. "href", the text content if unset
+//!             "html": Boolean // take the inner html instead of the text content
+//!         }?
+//!     }
+//! }
+//! ```
+//!
+//! **feed** passes through the items of RSS 2.0, Atom and JSON Feed origins, so they can be re-hosted
+//! or merged with other origins. It takes no options. The Atom `rel="next"` link and JSON Feed
+//! `next_url` are used as the continuation.
+//!
+//! ```json
+//! {
+//!     "extractor-name": "feed"
+//! }
+//! ```
+//!
+//! **json** maps JSONPaths onto item fields for JSON APIs.
+//!
+//! ```json
+//! {
+//!     "extractor-name": "json",
+//!     "json": {
+//!         "items": String, // path to the items from the response, i.e. "$.data.posts[*]"
+//!         "next": String?, // path to the next page url or cursor from the response, i.e. "$.meta.cursor"
+//!         "next-url": String?, // next page url with "{next}" replaced by the percent-encoded cursor, i.e. "/posts?cursor={next}"
+//!         "title" | "link" | "description" | "author" | "category" | "comments" | "guid" | "pubDate" | "content": String? // path from each item, i.e. "$.attributes.title"
+//!     }
+//! }
+//! ```
+//!
+//! **regex** turns each match of a regex over the response into an item, for pages without usable
+//! markup such as plain text changelogs.
+//!
+//! ```json
+//! {
+//!     "extractor-name": "regex",
+//!     "regex": {
+//!         "pattern": String, // regex with named groups, i.e. "(?m)^## (?P<title>\\S+) \\((?P<date>[\\d-]+)\\)"
+//!         "title" | "link" | "description" | "author" | "category" | "comments" | "guid" | "pubDate" | "content": String? // template with "{name}" replaced by that group, i.e. "{date}T00:00:00Z"
+//!     }
+//! }
+//! ```
+//!
+//! Fields without a template take the group of the same name. Inline flags such as `(?s)` let `.` match newlines.
+//!
+//! **wasm** runs a WebAssembly module in a sandbox, for extractors that should not get shell access.
+//! It is only built with the `wasm` feature of the crate.
+//! The module is a WASI (preview 1) command, i.e. built for `wasm32-wasip1`, that reads the extractor
+//! script input from stdin and writes the output to stdout. It has no access to files, the network,
+//! environment variables or arguments, and up to 64 KiB of stderr goes to the log.
+//!
+//! ```json
+//! {
+//!     "extractor-name": "wasm",
+//!     "wasm": {
+//!         "module": String, // path to the .wasm file, recompiled when it changes
+//!         "fuel": Number, // maximum fuel consumed by each page, roughly the number of instructions
+//!         "max-memory": Number // maximum number of bytes of memory, also limits the output size
+//!     }
+//! }
+//! ```
+//!
+//! Modules that run out of fuel or memory, or run longer than "script-timeout", fail the fetch.
+//!
+//! **rhai** runs a [Rhai](https://rhai.rs) script in process, so simple extractors need no other runtime.
+//!
+//! ```json
+//! {
+//!     "extractor-name": "rhai",
+//!     "rhai": {
+//!         "file": String?, // path to the script
+//!         "script": String?, // the script itself, if "file" is unset
+//!         "max-operations": Number // maximum number of operations each page, the fetch fails when exceeded or after "script-timeout"
+//!     }
+//! }
+//! ```
+//!
+//! The script gets the constants `url`, `webstr` (`()` if not fetched) and `preexists`, and returns either
+//! an array of items or an object like the extractor script output. Items are object maps of PseudoItem
+//! fields, relative links and continuations are resolved against the page url.
+//!
+//! HTML can be queried with `parse_html(webstr)`, which returns the root element. Elements have
+//! `select(selector)` returning an array of elements, `select_first(selector)` returning an element or
+//! `()`, `attr(name)`, and the `text` and `html` properties.
+//!
+//! ```rhai
+//! let doc = parse_html(webstr);
+//! let items = [];
+//! for post in doc.select(".post") {
+//!     let link = post.select_first("h2 a");
+//!     items.push(#{ title: link.text, link: link.attr("href") });
 //! }
+//! items
 //! ```
 
 mod bindings;
 pub use bindings::*;
+mod extractors;
+pub use extractors::*;
 mod options;
 pub use options::*;
 mod traits;
 pub use traits::*;
+mod charset;
+mod clients;
+mod cookies;
+mod fixtures;
 mod locks;
+mod ratelimit;
+mod response;
+mod robots;
+pub use robots::*;
+mod workers;
 pub use values::*;
 mod values;
 pub use errors::*;
@@ -1,30 +1,38 @@
-use std::{collections::HashMap, sync::OnceLock};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+};
 
 use tokio::sync::Mutex;
 
-pub struct Locks(pub OnceLock<HashMap<String, Mutex<()>>>);
+pub struct Locks(pub OnceLock<std::sync::Mutex<HashMap<String, Arc<Mutex<()>>>>>);
 
 impl Locks {
     pub const fn new() -> Self {
         Self(OnceLock::new())
     }
+
+    /// Get the lock under a key, creating one if it does not exist
+    pub fn get(&self, key: String) -> Arc<Mutex<()>> {
+        self.0
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .clone()
+    }
+}
+
+impl Default for Locks {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[macro_export]
 macro_rules! take_lock {
     ($locks: expr, $key: expr) => {
-        unsafe {
-            if $locks.0.get().is_none() {
-                $locks.0.set(std::collections::HashMap::default()).unwrap();
-            }
-            $locks
-                .0
-                .get_mut()
-                .unwrap()
-                .entry($key)
-                .or_insert(tokio::sync::Mutex::default())
-                .lock()
-                .await
-        }
+        $locks.get($key).lock_owned().await
     };
 }
@@ -0,0 +1,137 @@
+use std::{error::Error, path::PathBuf};
+
+use reqwest::{RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+
+/// A secret value read from outside of the config file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Secret {
+    /// Name of an environment variable
+    Env(String),
+    /// Path to a file containing the secret
+    File(PathBuf),
+}
+
+impl Secret {
+    /// Read the secret value
+    pub fn resolve(&self) -> Result<String, Box<dyn Error>> {
+        match self {
+            Self::Env(name) => std::env::var(name)
+                .map_err(|_| crate::Error::MissingSecret(format!("env {name}")).into()),
+            Self::File(path) => match std::fs::read_to_string(path) {
+                Ok(content) => Ok(content.trim_end_matches(['\r', '\n']).to_string()),
+                Err(_) => Err(crate::Error::MissingSecret(format!(
+                    "file {}",
+                    path.to_string_lossy()
+                ))
+                .into()),
+            },
+        }
+    }
+}
+
+/// Authentication for requests to the origin host
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum AuthOption {
+    /// HTTP basic auth
+    Basic { username: String, password: Secret },
+    /// Authorization: Bearer token
+    Bearer { token: Secret },
+    /// API key in a request header
+    Header { name: String, value: Secret },
+    /// API key in a query parameter
+    Query { name: String, value: Secret },
+}
+
+impl AuthOption {
+    /// The secret used by this method
+    pub fn secret(&self) -> &Secret {
+        match self {
+            Self::Basic { password, .. } => password,
+            Self::Bearer { token } => token,
+            Self::Header { value, .. } | Self::Query { value, .. } => value,
+        }
+    }
+
+    /// Add authentication to a request
+    pub fn apply(&self, request: RequestBuilder) -> Result<RequestBuilder, Box<dyn Error>> {
+        let secret = self.secret().resolve()?;
+
+        Ok(match self {
+            Self::Basic { username, .. } => request.basic_auth(username, Some(secret)),
+            Self::Bearer { .. } => request.bearer_auth(secret),
+            Self::Header { name, .. } => request.header(name, secret),
+            Self::Query { name, .. } => request.query(&[(name, secret)]),
+        })
+    }
+
+    /// Remove the query parameter holding the secret from a url a request was sent to
+    pub fn redact(&self, url: &mut Url) {
+        let Self::Query { name, .. } = self else {
+            return;
+        };
+
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| key != name)
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        if pairs.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Url;
+
+    use super::{AuthOption, Secret};
+    use crate::response::read_body;
+
+    #[test]
+    fn redact_removes_only_the_query_secret() {
+        let query = AuthOption::Query {
+            name: "key".to_string(),
+            value: Secret::Env("KEY".to_string()),
+        };
+
+        let mut url = Url::parse("https://example.com/a?page=2&key=secret").unwrap();
+        query.redact(&mut url);
+        assert_eq!(url.as_str(), "https://example.com/a?page=2");
+
+        let mut url = Url::parse("https://example.com/a?key=secret").unwrap();
+        query.redact(&mut url);
+        assert_eq!(url.as_str(), "https://example.com/a");
+
+        let header = AuthOption::Header {
+            name: "key".to_string(),
+            value: Secret::Env("KEY".to_string()),
+        };
+        let mut url = Url::parse("https://example.com/a?key=value").unwrap();
+        header.redact(&mut url);
+        assert_eq!(url.as_str(), "https://example.com/a?key=value");
+    }
+    #[tokio::test]
+    async fn body_too_large_errors_name_the_redacted_url() {
+        let query = AuthOption::Query {
+            name: "key".to_string(),
+            value: Secret::Env("KEY".to_string()),
+        };
+
+        let mut url = Url::parse("https://example.com/a?key=secret").unwrap();
+        query.redact(&mut url);
+        let res = reqwest::Response::from(http::Response::new("too long"));
+        let e = read_body(res, url.as_str(), 3).await.err().unwrap();
+
+        assert!(matches!(
+            e.downcast_ref::<crate::Error>(),
+            Some(crate::Error::BodyTooLarge { url, limit: 3 }) if url == "https://example.com/a"
+        ));
+    }
+}
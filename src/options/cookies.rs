@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// Cookie jar options for a feed
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct CookieOption {
+    /// Save cookies to `cookies.json` so they outlive the process
+    #[serde_inline_default(true)]
+    pub persist: bool,
+    /// Cookies set before the first request, in Set-Cookie format relative to the origin
+    #[serde(default)]
+    pub initial: Vec<String>,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+use super::ItemFields;
+
+/// Options for the built-in CSS selector extractor
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct CssOption {
+    /// Selector of each item
+    #[serde_inline_default("article".to_string())]
+    pub item: String,
+    /// Selector of the link to the next page, used as the continuation
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    /// Selectors of each item field, within the item
+    #[serde(flatten)]
+    #[serde(default)]
+    pub fields: ItemFields<CssField>,
+}
+
+/// Where to find the value of an item field
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct CssField {
+    /// Selector within the item, the item itself if unset
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    /// Attribute to take the value from, the text content if unset
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attr: Option<String>,
+    /// Take the inner html instead of the text content
+    #[serde_inline_default(false)]
+    pub html: bool,
+}
@@ -1,7 +1,19 @@
-use std::{collections::HashMap, error::Error, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use async_recursion::async_recursion;
+use base64::{engine::general_purpose::STANDARD, Engine};
 use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, CONTENT_TYPE, RETRY_AFTER},
+    redirect::Policy,
+    Client, StatusCode, Url,
+};
 use serde::{Deserialize, Serialize};
 use serde_default::DefaultFromSerde;
 use serde_inline_default::serde_inline_default;
@@ -9,14 +21,25 @@ use subprocess::{Exec, Redirection};
 use tokio::{fs, io::AsyncWriteExt, task::spawn_blocking};
 
 use crate::{
-    bindings::{ItemizerArg, ItemizerRes, PseudoChannel, PseudoItem},
+    bindings::{ItemizerArg, ItemizerRes, ItemizerResponse, PseudoChannel, PseudoItem},
+    charset,
+    cookies::CookieJar,
+    errors::sendable,
+    fixtures::Fixture,
+    response::{read_body, RawResponse},
+    robots::RobotsTxt,
     take_lock,
     traits::Saveable,
-    values::{LOCKS, MASTER},
+    values::{CLIENT, CLIENTS, EXTRACTORS, LIMITER, LOCKS, MASTER, WORKERS},
     PseudoItemCache,
 };
 
-use super::fetched::FetchedMeta;
+use super::{
+    fetched::{CacheValidators, FetchedMeta},
+    AuthOption, CookieOption, CssOption, FixtureMode, FixtureOption, HttpOption, JsonOption,
+    PaginationMode, PaginationOption, RateLimitOption, RegexOption, RequestOption, RetryOption,
+    RhaiOption, WasmOption,
+};
 
 /// Array of feeds to fetch
 #[serde_inline_default]
@@ -74,7 +97,7 @@ impl Feeds {
                         let _lock = take_lock!(LOCKS, feed.label.clone());
 
                         let mut meta = FetchedMeta::load_json(&meta_path).await.unwrap_or_default();
-                        if let Err(e) = feed.fetch_items_noreturn(&meta).await {
+                        if let Err(e) = feed.fetch_items_noreturn(&mut meta).await {
                             println!("Error fetching feed: {e}");
                         }
 
@@ -92,6 +115,19 @@ impl Feeds {
     }
 }
 
+/// How non-text responses are passed to the extractor
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum BinaryMode {
+    /// Decode as text anyway in `webstr`
+    #[default]
+    Text,
+    /// Base64 encoded in `webbase64`
+    Base64,
+    /// Saved to a file with the path in `webfile`
+    File,
+}
+
 /// Specific scraping options for a single feed
 #[serde_inline_default]
 #[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
@@ -99,6 +135,14 @@ pub struct FeedOption {
     /// URL to fetch
     #[serde_inline_default("https://feeds.bbci.co.uk/news/world/rss.xml".to_string())]
     pub origin: String,
+    /// Additional URLs to fetch, merged into the same feed
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub origins: Vec<String>,
+    /// Values to substitute for `{value}` in the origins, each giving its own URL
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
     /// Text label of the feed
     #[serde_inline_default("default-feed-name".to_string())]
     pub label: String,
@@ -123,8 +167,96 @@ pub struct FeedOption {
     /// Scraper script
     #[serde_inline_default(vec!["/usr/bin/node".to_string(), "/path/to/script.js".to_string()])]
     pub extractor: Vec<String>,
+    /// Name of a registered in process extractor, used instead of the extractor command
+    #[serde(rename = "extractor-name")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extractor_name: Option<String>,
+    /// Options for the built-in css extractor
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub css: Option<CssOption>,
+    /// Options for the built-in json extractor
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<JsonOption>,
+    /// Options for the built-in regex extractor
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex: Option<RegexOption>,
+    /// Options for the built-in Rhai script extractor
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rhai: Option<RhaiOption>,
+    /// Options for the built-in WebAssembly extractor
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm: Option<WasmOption>,
+    /// Keep the extractor running and exchange newline delimited JSON over stdin and stdout
+    #[serde_inline_default(false)]
+    pub worker: bool,
     #[serde_inline_default(true)]
     pub fetch: bool,
+    /// Find pages from the origin instead of following the extractor's continuation
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<PaginationOption>,
+    /// Maximum number of pages to fetch from each origin
+    #[serde(rename = "max-pages")]
+    #[serde_inline_default(20)]
+    pub max_pages: usize,
+    /// Stop following pages after one with no new items
+    #[serde(rename = "stop-at-known")]
+    #[serde_inline_default(false)]
+    pub stop_at_known: bool,
+    /// Stop following pages after this many items already in the cache are found
+    #[serde(rename = "known-limit")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub known_limit: Option<usize>,
+    /// How non-text responses are passed to the extractor
+    #[serde(default)]
+    pub binary: BinaryMode,
+    /// Maximum number of bytes in a response body, overrides the global option
+    #[serde(rename = "max-body-size")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_body_size: Option<u64>,
+    /// HTTP request options
+    #[serde(default)]
+    pub request: RequestOption,
+    /// HTTP client options, replaces the global options for this feed
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http: Option<HttpOption>,
+    /// Rate limit options, replaces the global options for this feed
+    #[serde(rename = "rate-limit")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitOption>,
+    /// Check robots.txt before each request, overrides the global option
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub robots: Option<bool>,
+    /// Retry delay options, replaces the global options for this feed
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryOption>,
+    /// Cookie jar shared by the origin and continuations
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cookies: Option<CookieOption>,
+    /// Authentication for requests to the origin host
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthOption>,
+    /// Pass the resolved auth secret to the extractor
+    #[serde(rename = "expose-secret")]
+    #[serde_inline_default(false)]
+    pub expose_secret: bool,
+    /// Record or replay responses
+    #[serde(default)]
+    pub fixtures: FixtureOption,
 
     /// Channel details
     #[serde(default)]
@@ -133,9 +265,9 @@ pub struct FeedOption {
 }
 
 impl FeedOption {
-    /// Check if the extractor command is valid (not empty)
+    /// Check if the extractor command is valid (not empty), unless a named extractor is used
     pub fn validate(&self) {
-        if self.extractor.is_empty() {
+        if self.extractor.is_empty() && self.extractor_name.is_none() {
             panic!("empty extractor")
         }
     }
@@ -168,10 +300,7 @@ impl FeedOption {
         let mut meta = FetchedMeta::load_json(&meta_path).await?;
 
         if self.outdated(&meta) {
-            self.fetch_items_noreturn(&meta).await?;
-            meta.fetched();
-            meta.requested();
-            meta.save_json(&meta_path).await?;
+            self.fetch_items_record(&mut meta, &meta_path).await?;
 
             return PseudoChannel::load_string(&rss_path).await;
         }
@@ -196,10 +325,7 @@ impl FeedOption {
             .join(&self.label)
             .join("meta.json");
         let mut meta = FetchedMeta::load_json(&meta_path).await?;
-        self.fetch_items_noreturn(&meta).await?;
-        meta.fetched();
-        meta.requested();
-        meta.save_json(&meta_path).await?;
+        self.fetch_items_record(&mut meta, &meta_path).await?;
 
         PseudoChannel::load_string(&rss_path).await
     }
@@ -222,10 +348,7 @@ impl FeedOption {
         let mut meta = FetchedMeta::load_json(&meta_path).await?;
 
         if self.outdated(&meta) {
-            self.fetch_items_noreturn(&meta).await?;
-            meta.fetched();
-            meta.requested();
-            meta.save_json(&meta_path).await?;
+            self.fetch_items_record(&mut meta, &meta_path).await?;
 
             return PseudoChannel::load_string(&json_path).await;
         }
@@ -250,10 +373,7 @@ impl FeedOption {
             .join(&self.label)
             .join("meta.json");
         let mut meta = FetchedMeta::load_json(&meta_path).await?;
-        self.fetch_items_noreturn(&meta).await?;
-        meta.fetched();
-        meta.requested();
-        meta.save_json(&meta_path).await?;
+        self.fetch_items_record(&mut meta, &meta_path).await?;
 
         PseudoChannel::load_string(&json_path).await
     }
@@ -269,10 +389,7 @@ impl FeedOption {
         let mut meta = FetchedMeta::load_json(&meta_path).await?;
 
         if self.outdated(&meta) {
-            let fetched = self.fetch_items_return(&meta).await?;
-            meta.fetched();
-            meta.requested();
-            meta.save_json(&meta_path).await?;
+            let fetched = self.fetch_items_record(&mut meta, &meta_path).await?;
             return Ok(self.channel.clone().with_items(fetched));
         }
 
@@ -297,14 +414,83 @@ impl FeedOption {
             .join(&self.label)
             .join("meta.json");
         let mut meta = FetchedMeta::load_json(&meta_path).await?;
-        let items = self.fetch_items_return(&meta).await?;
-        meta.fetched();
-        meta.requested();
-        meta.save_json(&meta_path).await?;
+        let items = self.fetch_items_record(&mut meta, &meta_path).await?;
 
         Ok(self.channel.clone().with_items(items))
     }
 
+    /// HTTP client used for fetching this feed
+    pub fn client(&self) -> Result<Client, Box<dyn Error>> {
+        if self.http.is_none() && self.cookies.is_none() && self.auth.is_none() {
+            return Ok(CLIENT.get().unwrap().clone());
+        }
+
+        CLIENTS.get(&self.label, || {
+            let http = self.http.as_ref().unwrap_or(&MASTER.get().unwrap().http);
+            let mut builder = http.builder()?;
+
+            // headers other than Authorization are kept on redirects, so they must stay on the origins
+            if self.auth.is_some() {
+                let hosts: Vec<String> = self
+                    .all_origins()
+                    .iter()
+                    .filter_map(|origin| Some(Url::parse(origin).ok()?.host_str()?.to_string()))
+                    .collect();
+                let max_redirects = http.max_redirects;
+
+                builder = builder.redirect(Policy::custom(move |attempt| {
+                    if attempt.previous().len() > max_redirects {
+                        attempt.error("too many redirects")
+                    } else if hosts
+                        .iter()
+                        .any(|host| Some(host.as_str()) == attempt.url().host_str())
+                    {
+                        attempt.follow()
+                    } else {
+                        let url = attempt.url().to_string();
+                        attempt.error(crate::Error::CrossHostRedirect(url))
+                    }
+                }));
+            }
+
+            if let Some(cookies) = &self.cookies {
+                let path = MASTER
+                    .get()
+                    .unwrap()
+                    .store
+                    .join(&self.label)
+                    .join("cookies.json");
+                let jar = CookieJar::load(path, &self.origin, cookies)?;
+                builder = builder.cookie_provider(Arc::new(jar));
+            }
+
+            Ok(builder.build()?)
+        })
+    }
+
+    /// Rate limit options used for this feed
+    pub fn rate_limit_option(&self) -> &RateLimitOption {
+        self.rate_limit
+            .as_ref()
+            .unwrap_or(&MASTER.get().unwrap().rate_limit)
+    }
+
+    /// Maximum number of bytes in a response body for this feed
+    pub fn body_limit(&self) -> u64 {
+        self.max_body_size
+            .unwrap_or(MASTER.get().unwrap().max_body_size)
+    }
+
+    /// Check if validators from the last fetch should be used, recorded responses should always have a body
+    fn conditional(&self) -> bool {
+        self.request.conditional && self.fixtures.mode != FixtureMode::Record
+    }
+
+    /// Retry delay options used for this feed
+    pub fn retry_option(&self) -> &RetryOption {
+        self.retry.as_ref().unwrap_or(&MASTER.get().unwrap().retry)
+    }
+
     /// Check if a feed is outdated
     pub fn outdated(&self, meta: &FetchedMeta) -> bool {
         meta.last_fetch + self.interval < Utc::now().timestamp() as u64
@@ -323,7 +509,7 @@ impl FeedOption {
     /// Fetch and save cache to files, and return the value
     async fn fetch_items_return(
         &self,
-        meta: &FetchedMeta,
+        meta: &mut FetchedMeta,
     ) -> Result<Vec<PseudoItem>, Box<dyn Error>> {
         let rss_path = MASTER
             .get()
@@ -358,30 +544,53 @@ impl FeedOption {
             ),
         );
 
-        for i in 0..MASTER.get().unwrap().max_retries {
-            match self
-                .fetch_items_recurse(
-                    &mut items,
-                    json.0
-                        .clone()
-                        .into_iter()
-                        .map(|item| PseudoItem {
-                            content: None,
-                            ..item
-                        })
-                        .collect(),
-                    &self.origin,
-                    fetch_length as usize,
+        // validators are only trusted if there is a cache to fall back to
+        let known = if json.0.is_empty() {
+            HashMap::new()
+        } else {
+            meta.validators.clone()
+        };
+        let original: Vec<PseudoItem> = json
+            .0
+            .clone()
+            .into_iter()
+            .map(|item| PseudoItem {
+                content: None,
+                ..item
+            })
+            .collect();
+        let mut validators = HashMap::new();
+
+        // the first error is returned after saving the items fetched before it
+        let mut error = None;
+        let origins = self.origin_urls()?;
+        for origin in origins {
+            // items from earlier origins should not be extracted again
+            let mut preexists = original.clone();
+            preexists.extend(items.iter().cloned().map(|item| PseudoItem {
+                content: None,
+                ..item
+            }));
+
+            let mut fetched = Vec::new();
+            if let Err(e) = self
+                .fetch_items_retry(
+                    &mut fetched,
+                    &known,
+                    &mut validators,
+                    preexists,
+                    &origin,
+                    fetch_length,
                 )
                 .await
             {
-                Ok(()) => break,
-                Err(e) => println!("Error fetching {} on retry {}: {e}", self.origin, i + 1),
+                error.get_or_insert(sendable(e));
             }
-
-            items.clear()
+            items.append(&mut fetched);
         }
 
+        meta.validators = validators;
+
         items.iter_mut().for_each(|item| {
             if item.timestamp.is_some() {
                 return;
@@ -394,14 +603,7 @@ impl FeedOption {
                 })
             }
         });
-        items.append(&mut json.0);
-        if self.sort {
-            items.sort_by(|item, other| other.timestamp.cmp(&item.timestamp));
-        }
-
-        if items.len() > self.max_length {
-            items.drain(self.max_length..);
-        }
+        let items = self.merge_items(items, std::mem::take(&mut json.0));
 
         json.0 = items.clone();
         json.save_json(&json_path).await?;
@@ -413,132 +615,667 @@ impl FeedOption {
 
         rss.save_rss(&rss_path).await?;
 
-        Ok(items)
+        match error {
+            Some(e) => Err(e),
+            None => Ok(items),
+        }
     }
 
-    /// Fetch and save cache to files
-    async fn fetch_items_noreturn(&self, meta: &FetchedMeta) -> Result<(), Box<dyn Error>> {
-        let rss_path = MASTER
-            .get()
-            .unwrap()
-            .store
-            .join(&self.label)
-            .join("cache.xml");
-        let json_path = MASTER
-            .get()
-            .unwrap()
-            .store
-            .join(&self.label)
-            .join("cache.json");
+    /// Merge items fetched from all origins with the cached items, sorted and capped to max_length
+    ///
+    /// The first copy of an item is kept, so fetched items replace cached ones
+    fn merge_items(
+        &self,
+        mut items: Vec<PseudoItem>,
+        mut cached: Vec<PseudoItem>,
+    ) -> Vec<PseudoItem> {
+        items.append(&mut cached);
+
+        let mut unique: Vec<PseudoItem> = Vec::with_capacity(items.len());
+        for item in items {
+            if !unique.contains(&item) {
+                unique.push(item);
+            }
+        }
+        let mut items = unique;
 
-        let mut json = if fs::try_exists(&json_path).await? {
-            PseudoItemCache::load_json(&json_path).await.unwrap_or_else(|_| {
-                let new_path = json_path.with_file_name(format!("cache-{}.json", chrono::Utc::now().to_rfc3339()));
-                println!("Could not load json for {}, continuing with default.\nOld file has been moved to {}", self.label, new_path.to_string_lossy());
-                PseudoItemCache::default()
-            })
-        } else {
-            PseudoItemCache::default()
-        };
+        if self.sort {
+            items.sort_by_key(|item| std::cmp::Reverse(item.timestamp));
+        }
 
-        let mut items = Vec::new();
-        let fetch_length = std::cmp::min(
-            self.max_length,
-            std::cmp::max(
-                ((chrono::Utc::now().timestamp() as u64 - meta.last_fetch + 1) / self.interval
-                    * self.fetch_length as u64) as usize,
-                self.fetch_length,
-            ),
-        );
+        if items.len() > self.max_length {
+            items.drain(self.max_length..);
+        }
+
+        items
+    }
 
-        for i in 0..MASTER.get().unwrap().max_retries {
-            match self
-                .fetch_items_recurse(
-                    &mut items,
-                    json.0
-                        .clone()
-                        .into_iter()
-                        .map(|item| PseudoItem {
-                            content: None,
-                            ..item
-                        })
-                        .collect(),
-                    &self.origin,
-                    fetch_length as usize,
+    /// Fetch and save cache to files, then record the fetch in the meta at meta_path
+    ///
+    /// Failed fetches are recorded too, so an origin that keeps failing is only retried after the interval
+    async fn fetch_items_record(
+        &self,
+        meta: &mut FetchedMeta,
+        meta_path: &Path,
+    ) -> Result<Vec<PseudoItem>, Box<dyn Error>> {
+        let items = self.fetch_items_return(meta).await.map_err(sendable);
+        meta.fetched();
+        meta.requested();
+        meta.save_json(meta_path).await?;
+        items.map_err(|e| e as Box<dyn Error>)
+    }
+
+    /// Fetch and save cache to files
+    async fn fetch_items_noreturn(&self, meta: &mut FetchedMeta) -> Result<(), Box<dyn Error>> {
+        self.fetch_items_return(meta).await?;
+        Ok(())
+    }
+
+    /// Fetch items from an origin into items, retrying on errors
+    ///
+    /// Items from pages fetched before the last error are kept
+    async fn fetch_items_retry(
+        &self,
+        items: &mut Vec<PseudoItem>,
+        known: &HashMap<String, CacheValidators>,
+        validators: &mut HashMap<String, CacheValidators>,
+        original: Vec<PseudoItem>,
+        origin: &str,
+        fetch_length: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        let max_retries = MASTER.get().unwrap().max_retries;
+
+        for i in 0..max_retries {
+            let mut fetched = HashMap::new();
+            let delay = match self
+                .fetch_items_origin(
+                    items,
+                    known,
+                    &mut fetched,
+                    original.clone(),
+                    origin,
+                    fetch_length,
                 )
                 .await
             {
-                Ok(()) => break,
-                Err(e) => println!("Error fetching {} on retry {}: {e}", self.origin, i + 1),
+                Ok(()) => {
+                    validators.extend(fetched);
+                    return Ok(());
+                }
+                Err(e) => {
+                    println!("Error fetching {origin} on retry {}: {e}", i + 1);
+                    match self.retry_option().delay(e.as_ref(), i) {
+                        Some(delay) if i + 1 < max_retries => delay,
+                        _ => return Err(e),
+                    }
+                }
+            };
+
+            items.clear();
+            tokio::time::sleep(delay).await;
+        }
+
+        Ok(())
+    }
+
+    /// Fetch items from an origin once, with either the pagination options or continuations
+    async fn fetch_items_origin(
+        &self,
+        items: &mut Vec<PseudoItem>,
+        known: &HashMap<String, CacheValidators>,
+        validators: &mut HashMap<String, CacheValidators>,
+        original: Vec<PseudoItem>,
+        origin: &str,
+        fetch_length: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        match &self.pagination {
+            Some(pagination) => {
+                self.fetch_items_paginate(
+                    pagination,
+                    items,
+                    known,
+                    validators,
+                    original,
+                    origin,
+                    fetch_length,
+                )
+                .await
+            }
+            None => {
+                self.fetch_items_recurse(
+                    items,
+                    known,
+                    validators,
+                    &mut HashSet::from([origin.to_string()]),
+                    &mut 0,
+                    original,
+                    origin,
+                    origin,
+                    fetch_length,
+                )
+                .await
             }
+        }
+    }
 
-            items.clear()
+    /// Origins of the feed, with `{value}` expanded over values when there are any
+    pub fn all_origins(&self) -> Vec<String> {
+        let origins = std::iter::once(&self.origin).chain(self.origins.iter());
+
+        if self.values.is_empty() {
+            return origins.cloned().collect();
         }
 
-        items.iter_mut().for_each(|item| {
-            if item.timestamp.is_some() {
-                return;
+        origins
+            .flat_map(|origin| {
+                if !origin.contains("{value}") {
+                    return vec![origin.clone()];
+                }
+
+                self.values
+                    .iter()
+                    .map(|value| origin.replace("{value}", value))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// URLs to fetch from all origins, with local directories and globs expanded to each file
+    pub fn origin_urls(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut urls = Vec::new();
+        for origin in self.all_origins() {
+            urls.append(&mut Self::expand_files(&origin)?);
+        }
+
+        Ok(urls)
+    }
+
+    /// Expand a file:// directory or glob to each file in it
+    fn expand_files(origin: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        if !origin.starts_with("file://") {
+            return Ok(vec![origin.to_string()]);
+        }
+
+        let path = Url::parse(origin)?
+            .to_file_path()
+            .map_err(|_| format!("invalid file url {origin}"))?;
+        let pattern = if path.is_dir() { path.join("*") } else { path };
+
+        let mut paths = Vec::new();
+        for entry in glob::glob(&pattern.to_string_lossy())? {
+            let entry = entry?;
+            if entry.is_file() {
+                paths.push(entry);
             }
+        }
+        paths.sort();
 
-            if let Some(pub_date) = &item.pub_date {
-                item.timestamp = Some(match DateTime::parse_from_rfc2822(pub_date) {
-                    Ok(date) => date.timestamp() as u64,
-                    Err(_) => return,
-                })
+        if paths.is_empty() {
+            return Err(crate::Error::MissingFiles(origin.to_string()).into());
+        }
+
+        Ok(paths
+            .into_iter()
+            .filter_map(|path| Url::from_file_path(path).ok())
+            .map(String::from)
+            .collect())
+    }
+
+    /// Send a request to url, applying robots.txt and rate limits
+    async fn send(
+        &self,
+        url: &str,
+        known: &HashMap<String, CacheValidators>,
+    ) -> Result<RawResponse, Box<dyn Error>> {
+        let client = self.client()?;
+        let parsed = Url::parse(url)?;
+        let mut request = self.request.build(&client, url)?;
+        if self.conditional() {
+            if let Some(cached) = known.get(url) {
+                request = cached.apply(request);
             }
-        });
-        items.append(&mut json.0);
-        if self.sort {
-            items.sort_by(|item, other| other.timestamp.cmp(&item.timestamp));
         }
 
-        if items.len() > self.max_length {
-            items.drain(self.max_length..);
+        // credentials are never sent to hosts other than the origins
+        if let Some(auth) = &self.auth {
+            let origin_host = self.all_origins().iter().any(|origin| {
+                Url::parse(origin).is_ok_and(|origin| origin.host_str() == parsed.host_str())
+            });
+
+            if origin_host {
+                request = auth.apply(request)?;
+            }
         }
 
-        json.0 = items.clone();
-        json.save_json(&json_path).await?;
+        let host = parsed.host_str().unwrap_or_default();
+        let mut rate_limit = self.rate_limit_option().clone();
 
-        let rss = PseudoChannel {
-            items: Some(items),
-            ..self.channel.clone()
+        if self.robots.unwrap_or(MASTER.get().unwrap().robots.enabled) {
+            let option = &MASTER.get().unwrap().robots;
+            let robots = RobotsTxt::load(&client, &parsed, option, &rate_limit).await?;
+            let path = match parsed.query() {
+                Some(query) => format!("{}?{query}", parsed.path()),
+                None => parsed.path().to_string(),
+            };
+
+            if !robots.allowed(&option.user_agent, &path) {
+                return Err(crate::Error::RobotsDisallowed(url.to_string()).into());
+            }
+
+            if let Some(delay) = robots.crawl_delay(&option.user_agent) {
+                rate_limit.min_delay = rate_limit.min_delay.max((delay * 1000.) as u64);
+            }
+        }
+
+        let _permit = LIMITER.acquire(host, &rate_limit).await;
+
+        tokio::select! {
+            res = async {
+                let res = match request.send().await {
+                    Ok(res) => res,
+                    Err(e) => {
+                        // errors from the redirect policy are not retried
+                        if let Some(crate::Error::CrossHostRedirect(url)) =
+                            e.source().and_then(|e| e.downcast_ref::<crate::Error>())
+                        {
+                            return Err(crate::Error::CrossHostRedirect(url.clone()).into());
+                        }
+
+                        return Err(e.into());
+                    }
+                };
+                let mut url = res.url().clone();
+                if let Some(auth) = &self.auth {
+                    auth.redact(&mut url);
+                }
+                let url = url.to_string();
+                let status = res.status();
+                let headers = res.headers().clone();
+                let body = if status == StatusCode::NOT_MODIFIED {
+                    Vec::new()
+                } else {
+                    read_body(res, &url, self.body_limit()).await?
+                };
+
+                Ok::<_, Box<dyn Error + Send + Sync>>(RawResponse { url, status, headers, body })
+            } => {
+                res.map_err(|e| match e.downcast::<reqwest::Error>() {
+                    // the url of the request holds the secret
+                    Ok(e) if matches!(self.auth, Some(AuthOption::Query { .. })) => {
+                        e.without_url().into()
+                    }
+                    Ok(e) => e as Box<dyn Error>,
+                    Err(e) => e,
+                })
+            },
+            _ = tokio::time::sleep(Duration::from_secs(MASTER.get().unwrap().request_timeout)) => {
+                Err(crate::Error::Timedout.into())
+            }
+        }
+    }
+
+    /// Directory of recorded responses
+    pub fn fixtures_dir(&self) -> PathBuf {
+        self.fixtures.dir.clone().unwrap_or_else(|| {
+            MASTER
+                .get()
+                .unwrap()
+                .store
+                .join(&self.label)
+                .join("fixtures")
+        })
+    }
+
+    /// Response for url of origin from recorded fixtures, a local file or the network
+    ///
+    /// Local files are only read for file:// origins, as pages could otherwise link to any file
+    async fn respond(
+        &self,
+        url: &str,
+        origin: &str,
+        known: &HashMap<String, CacheValidators>,
+    ) -> Result<RawResponse, Box<dyn Error>> {
+        if url.starts_with("file://") && !origin.starts_with("file://") {
+            return Err(crate::Error::LocalFileDenied(url.to_string()).into());
+        }
+
+        let res = match self.fixtures.mode {
+            FixtureMode::Replay => Fixture::replay(&self.fixtures_dir(), url).await?,
+            _ if url.starts_with("file://") => {
+                let unconditional = HashMap::new();
+                let known = if self.conditional() {
+                    known
+                } else {
+                    &unconditional
+                };
+                RawResponse::read_file(url, known, self.body_limit()).await?
+            }
+            _ => self.send(url, known).await?,
         };
 
-        rss.save_rss(&rss_path).await?;
+        if self.fixtures.mode == FixtureMode::Record {
+            Fixture::record(&self.fixtures_dir(), url, &res).await?;
+        }
 
-        Ok(())
+        Ok(res)
     }
 
     /// Private recursive function to fetch items
+    #[allow(clippy::too_many_arguments)]
     #[async_recursion]
     async fn fetch_items_recurse(
         &self,
         items: &mut Vec<PseudoItem>,
+        known: &HashMap<String, CacheValidators>,
+        validators: &mut HashMap<String, CacheValidators>,
+        visited: &mut HashSet<String>,
+        known_items: &mut usize,
         original: Vec<PseudoItem>,
+        origin: &str,
         url: &str,
         fetch_length: usize,
     ) -> Result<(), Box<dyn Error>> {
         let mut preexists = original.clone();
         preexists.append(&mut items.clone());
 
-        let arg = ItemizerArg {
-            url: url.to_string(),
-            webstr: if self.fetch {
-                Some(tokio::select! {
-                    res = reqwest::get(url).await?.text() => {
-                        res?
-                    },
-                    _ = tokio::time::sleep(Duration::from_secs(MASTER.get().unwrap().request_timeout)) => {
-                        return Err(crate::Error::Timedout.into());
+        let res = if self.fetch {
+            Some(self.respond(url, origin, known).await?)
+        } else {
+            None
+        };
+        let length_left = fetch_length.saturating_sub(items.len());
+        let Some(res) = self
+            .extract(known, validators, preexists, url, res, length_left)
+            .await?
+        else {
+            return Ok(());
+        };
+        let start = items.len();
+        items.extend(res.items);
+
+        if items.len() >= self.max_length
+            || self.reached_known(&items[start..], res.known, known_items, &original)
+        {
+            return Ok(());
+        }
+
+        if let Some(continuation) = res.continuation {
+            if visited.len() >= self.max_pages {
+                return Ok(());
+            }
+
+            if !visited.insert(continuation.clone()) {
+                return Err(crate::Error::ContinuationLoop {
+                    from: url.to_string(),
+                    url: continuation,
+                }
+                .into());
+            }
+
+            self.fetch_items_recurse(
+                items,
+                known,
+                validators,
+                visited,
+                known_items,
+                original,
+                origin,
+                continuation.as_str(),
+                fetch_length,
+            )
+            .await?
+        }
+
+        Ok(())
+    }
+
+    /// Fetch items from pages of an origin found by the pagination options
+    ///
+    /// Stops at the first page with no items, or when there are enough items
+    #[allow(clippy::too_many_arguments)]
+    async fn fetch_items_paginate(
+        &self,
+        pagination: &PaginationOption,
+        items: &mut Vec<PseudoItem>,
+        known: &HashMap<String, CacheValidators>,
+        validators: &mut HashMap<String, CacheValidators>,
+        original: Vec<PseudoItem>,
+        origin: &str,
+        fetch_length: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        if pagination.mode == PaginationMode::Next {
+            let mut url = origin.to_string();
+            let mut visited = HashSet::from([url.clone()]);
+            let mut known_items = 0;
+
+            for _ in 0..self.max_pages {
+                let res = if self.fetch {
+                    Some(self.respond(&url, origin, known).await?)
+                } else {
+                    None
+                };
+                let next = match &res {
+                    Some(res) => {
+                        let content_type = res
+                            .headers
+                            .get(CONTENT_TYPE)
+                            .and_then(|value| value.to_str().ok());
+                        pagination.next_url(&res.url, &charset::decode(&res.body, content_type))?
                     }
-                })
+                    None => None,
+                };
+
+                let mut preexists = original.clone();
+                preexists.extend(items.iter().cloned());
+                let length_left = fetch_length.saturating_sub(items.len());
+                let Some(res) = self
+                    .extract(known, validators, preexists, &url, res, length_left)
+                    .await?
+                else {
+                    return Ok(());
+                };
+
+                if res.items.is_empty() {
+                    return Ok(());
+                }
+                let start = items.len();
+                items.extend(res.items);
+
+                if self.reached_known(&items[start..], res.known, &mut known_items, &original) {
+                    return Ok(());
+                }
+
+                match next {
+                    Some(next) if items.len() < fetch_length => {
+                        if !visited.insert(next.clone()) {
+                            return Err(crate::Error::ContinuationLoop {
+                                from: url,
+                                url: next,
+                            }
+                            .into());
+                        }
+
+                        url = next
+                    }
+                    _ => return Ok(()),
+                }
+            }
+
+            return Ok(());
+        }
+
+        let mut known_items = 0;
+        let urls = (0..self.max_pages as u64)
+            .map(|index| pagination.page_url(origin, index))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for batch in urls.chunks(pagination.concurrency.max(1)) {
+            let responses = if self.fetch {
+                let responses = futures::future::join_all(batch.iter().map(|url| async {
+                    match self.respond(url, origin, known).await {
+                        Ok(res) => Ok(Some(res)),
+                        Err(e) => Err(sendable(e)),
+                    }
+                }))
+                .await;
+
+                responses
+                    .into_iter()
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| e as Box<dyn Error>)?
             } else {
-                None
+                batch.iter().map(|_| None).collect()
+            };
+
+            for (url, res) in batch.iter().zip(responses) {
+                let mut preexists = original.clone();
+                preexists.extend(items.iter().cloned());
+                let length_left = fetch_length.saturating_sub(items.len());
+                let Some(res) = self
+                    .extract(known, validators, preexists, url, res, length_left)
+                    .await?
+                else {
+                    return Ok(());
+                };
+
+                if res.items.is_empty() {
+                    return Ok(());
+                }
+                let start = items.len();
+                items.extend(res.items);
+
+                if items.len() >= fetch_length
+                    || self.reached_known(&items[start..], res.known, &mut known_items, &original)
+                {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check if the pages after one with the page items are already known and should be skipped
+    ///
+    /// known_items counts the known items of the pages so far, including those left out by the extractor
+    fn reached_known(
+        &self,
+        page: &[PseudoItem],
+        left_out: usize,
+        known_items: &mut usize,
+        original: &[PseudoItem],
+    ) -> bool {
+        let page_known = page.iter().filter(|item| original.contains(item)).count();
+        *known_items += left_out + page_known;
+
+        if self.stop_at_known && page_known == page.len() {
+            return true;
+        }
+
+        self.known_limit.is_some_and(|limit| *known_items >= limit)
+    }
+
+    /// Pass a page to the extractor and return its output
+    ///
+    /// Nothing is returned if the page is unchanged since the last fetch
+    async fn extract(
+        &self,
+        known: &HashMap<String, CacheValidators>,
+        validators: &mut HashMap<String, CacheValidators>,
+        preexists: Vec<PseudoItem>,
+        url: &str,
+        res: Option<RawResponse>,
+        length_left: usize,
+    ) -> Result<Option<ItemizerRes>, Box<dyn Error>> {
+        let (mut webstr, mut webbase64, mut webfile) = (None, None, None);
+        let response = if let Some(res) = res {
+            // page is unchanged, skip extracting it and anything after it
+            if res.status == StatusCode::NOT_MODIFIED {
+                if let Some(cached) = known.get(url) {
+                    validators.insert(url.to_string(), cached.clone());
+                }
+                return Ok(None);
+            }
+
+            if !res.status.is_success() && !self.request.accept_errors {
+                return Err(crate::Error::HttpStatus {
+                    status: res.status.as_u16(),
+                    retry_after: match res.status {
+                        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => {
+                            retry_after(&res.headers)
+                        }
+                        _ => None,
+                    },
+                }
+                .into());
+            }
+
+            let fetched = CacheValidators::from_headers(&res.headers);
+            if !fetched.is_empty() {
+                validators.insert(url.to_string(), fetched);
+            }
+            let response = ItemizerResponse::new(res.url, res.status, &res.headers, &res.body);
+            let content_type = res
+                .headers
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok());
+
+            match self.binary {
+                _ if charset::is_text(response.content_type.as_deref(), &res.body) => {
+                    webstr = Some(charset::decode(&res.body, content_type))
+                }
+                BinaryMode::Text => webstr = Some(charset::decode(&res.body, content_type)),
+                BinaryMode::Base64 => webbase64 = Some(STANDARD.encode(&res.body)),
+                BinaryMode::File => {
+                    let body_path = MASTER
+                        .get()
+                        .unwrap()
+                        .store
+                        .join(&self.label)
+                        .join("body.bin");
+                    fs::write(&body_path, &res.body).await?;
+                    webfile = Some(body_path.to_string_lossy().to_string());
+                }
+            }
+
+            Some(response)
+        } else {
+            None
+        };
+
+        let arg = ItemizerArg {
+            url: url.to_string(),
+            webstr,
+            webbase64,
+            webfile,
+            response,
+            secret: match &self.auth {
+                Some(auth) if self.expose_secret => Some(auth.secret().resolve()?),
+                _ => None,
             },
             preexists,
             feed: self.clone(),
-            length_left: fetch_length.checked_sub(items.len()).unwrap_or_default() as u32,
+            length_left: length_left as u32,
         };
+
+        if let Some(name) = &self.extractor_name {
+            let Some(extractor) = EXTRACTORS.get(name) else {
+                return Err(crate::Error::UnknownExtractor(name.clone()).into());
+            };
+
+            return tokio::select! {
+                res = extractor.extract(&arg) => Ok(Some(res?)),
+                _ = tokio::time::sleep(Duration::from_secs(MASTER.get().unwrap().script_timeout)) => {
+                    Err(crate::Error::FetchFailed.into())
+                }
+            };
+        }
+
+        if self.worker {
+            let timeout = Duration::from_secs(MASTER.get().unwrap().script_timeout);
+            return Ok(Some(WORKERS.request(&self.extractor, &arg, timeout).await?));
+        }
+
         let arg_path = MASTER
             .get()
             .unwrap()
@@ -620,17 +1357,193 @@ impl FeedOption {
                 return Err(e.into());
             }
         };
-        items.extend(res.items);
+        Ok(Some(res))
+    }
+}
 
-        if items.len() >= self.max_length {
-            return Ok(());
-        }
+/// Seconds to wait from a Retry-After header, either in seconds or as a HTTP date
+fn retry_after(headers: &HeaderMap) -> Option<u64> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
 
-        if let Some(continuation) = res.continuation {
-            self.fetch_items_recurse(items, original, continuation.as_str(), fetch_length)
-                .await?
+    match value.parse() {
+        Ok(secs) => Some(secs),
+        Err(_) => {
+            let date = DateTime::parse_from_rfc2822(value).ok()?;
+            Some((date.timestamp() - Utc::now().timestamp()).max(0) as u64)
         }
+    }
+}
 
-        Ok(())
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use reqwest::StatusCode;
+
+    use super::FeedOption;
+    use crate::{CacheValidators, MasterConfig, PseudoItem, MASTER};
+
+    #[test]
+    fn values_expand_only_origins_with_a_placeholder() {
+        let mut feed = FeedOption {
+            origin: "https://example.com/tag/{value}".to_string(),
+            origins: vec![
+                "https://example.com/news".to_string(),
+                "https://other.example/{value}/feed".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            feed.all_origins(),
+            [
+                "https://example.com/tag/{value}",
+                "https://example.com/news",
+                "https://other.example/{value}/feed"
+            ]
+        );
+
+        feed.values = vec!["rust".to_string(), "c".to_string()];
+        assert_eq!(
+            feed.all_origins(),
+            [
+                "https://example.com/tag/rust",
+                "https://example.com/tag/c",
+                "https://example.com/news",
+                "https://other.example/rust/feed",
+                "https://other.example/c/feed"
+            ]
+        );
+    }
+
+    #[test]
+    fn merged_items_keep_the_first_copy() {
+        let item = |title: &str, link: &str, timestamp: u64| PseudoItem {
+            title: Some(title.to_string()),
+            link: Some(link.to_string()),
+            timestamp: Some(timestamp),
+            ..Default::default()
+        };
+        let feed = FeedOption {
+            max_length: 3,
+            ..Default::default()
+        };
+
+        // the second origin also has /a, and the cache has an older copy of /b
+        let fetched = vec![
+            item("A", "/a", 3),
+            item("B", "/b", 5),
+            item("A again", "/a", 9),
+            item("C", "/c", 1),
+        ];
+        let cached = vec![item("B", "/b-old", 2), item("D", "/d", 4)];
+        let merged = feed.merge_items(fetched, cached);
+
+        let links: Vec<_> = merged.iter().map(|item| item.link.as_deref()).collect();
+        assert_eq!(links, [Some("/b"), Some("/d"), Some("/a")]);
+    }
+
+    #[tokio::test]
+    async fn pages_of_url_origins_cannot_lead_to_files() {
+        let feed = FeedOption::default();
+        let e = feed
+            .respond(
+                "file:///etc/passwd",
+                "https://example.com/",
+                &HashMap::new(),
+            )
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            e.downcast_ref::<crate::Error>(),
+            Some(crate::Error::LocalFileDenied(url)) if url == "file:///etc/passwd"
+        ));
+    }
+    #[tokio::test]
+    async fn unchanged_files_are_read_again_without_conditional() {
+        MASTER.get_or_init(MasterConfig::default);
+        let path = std::env::temp_dir().join("scrapyard-unconditional.html");
+        std::fs::write(&path, "<p>unchanged</p>").unwrap();
+        let url = reqwest::Url::from_file_path(&path).unwrap().to_string();
+
+        let mut feed = FeedOption::default();
+        let res = feed.respond(&url, &url, &HashMap::new()).await.unwrap();
+        let known = HashMap::from([(url.clone(), CacheValidators::from_headers(&res.headers))]);
+
+        let res = feed.respond(&url, &url, &known).await.unwrap();
+        assert_eq!(res.status, StatusCode::NOT_MODIFIED);
+
+        feed.request.conditional = false;
+        let res = feed.respond(&url, &url, &known).await.unwrap();
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(res.body, b"<p>unchanged</p>");
+    }
+    #[cfg(feature = "wasm")]
+    #[tokio::test]
+    async fn file_continuations_from_modules_fail_the_fetch() {
+        use reqwest::header::HeaderMap;
+
+        use crate::{
+            fixtures::Fixture, response::RawResponse, FixtureMode, FixtureOption, WasmOption,
+        };
+
+        MASTER.get_or_init(MasterConfig::default);
+        let dir = std::env::temp_dir().join("scrapyard-wasm-continuation");
+        std::fs::create_dir_all(&dir).unwrap();
+        let module = dir.join("continue.wat");
+        std::fs::write(
+            &module,
+            r#"(module
+                (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 16) "{\"items\":[{\"title\":\"a\"}],\"continuation\":\"file:///etc/passwd\"}")
+                (func (export "_start")
+                    (i32.store (i32.const 0) (i32.const 16))
+                    (i32.store (i32.const 4) (i32.const 61))
+                    (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8)))))"#,
+        )
+        .unwrap();
+
+        // the origin is replayed, so the test needs no network
+        let origin = "https://example.com/";
+        let page = RawResponse {
+            url: origin.to_string(),
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: b"page".to_vec(),
+        };
+        Fixture::record(&dir, origin, &page).await.unwrap();
+
+        let feed = FeedOption {
+            origin: origin.to_string(),
+            extractor_name: Some("wasm".to_string()),
+            wasm: Some(WasmOption {
+                module: module.to_string_lossy().to_string(),
+                ..Default::default()
+            }),
+            fixtures: FixtureOption {
+                mode: FixtureMode::Replay,
+                dir: Some(dir),
+            },
+            ..Default::default()
+        };
+        let e = feed
+            .fetch_items_origin(
+                &mut Vec::new(),
+                &HashMap::new(),
+                &mut HashMap::new(),
+                Vec::new(),
+                origin,
+                10,
+            )
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            e.downcast_ref::<crate::Error>(),
+            Some(crate::Error::LocalFileDenied(url)) if url == "file:///etc/passwd"
+        ));
     }
 }
@@ -1,3 +1,9 @@
+use std::collections::HashMap;
+
+use reqwest::{
+    header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    RequestBuilder,
+};
 use serde::{Deserialize, Serialize};
 use serde_default::DefaultFromSerde;
 use serde_inline_default::serde_inline_default;
@@ -16,6 +22,9 @@ pub struct FetchedMeta {
     #[serde(rename = "last-requested")]
     #[serde_inline_default(chrono::Utc::now().timestamp() as u64)]
     pub last_requested: u64,
+    /// Cache validators of each fetched url
+    #[serde(default)]
+    pub validators: HashMap<String, CacheValidators>,
 }
 
 impl Saveable for FetchedMeta {}
@@ -31,3 +40,50 @@ impl FetchedMeta {
         self.last_requested = chrono::Utc::now().timestamp() as u64;
     }
 }
+
+/// HTTP cache validators returned by a server
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct CacheValidators {
+    /// Value of the ETag header
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// Value of the Last-Modified header
+    #[serde(rename = "last-modified")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    /// Read validators from response headers
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let get = |key| {
+            headers
+                .get(key)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+
+        Self {
+            etag: get(ETAG),
+            last_modified: get(LAST_MODIFIED),
+        }
+    }
+
+    /// Check if there are no validators
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// Add conditional headers to a request
+    pub fn apply(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+
+        request
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Where to find each item field for a built-in extractor
+///
+/// Relative links are resolved against the page url, and dates are converted to RFC 2822
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct ItemFields<T> {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<T>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<T>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<T>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<T>,
+    /// Every value is a category
+    #[serde(rename = "category")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<T>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comments: Option<T>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guid: Option<T>,
+    /// RFC 2822 or RFC 3339 date, or a unix timestamp in seconds
+    #[serde(rename = "pubDate")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pub_date: Option<T>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<T>,
+}
+
+impl<T> ItemFields<T> {
+    /// Field by its name in feeds.json, i.e. "pubDate"
+    pub fn get(&self, name: &str) -> Option<&T> {
+        match name {
+            "title" => self.title.as_ref(),
+            "link" => self.link.as_ref(),
+            "description" => self.description.as_ref(),
+            "author" => self.author.as_ref(),
+            "category" => self.categories.as_ref(),
+            "comments" => self.comments.as_ref(),
+            "guid" => self.guid.as_ref(),
+            "pubDate" => self.pub_date.as_ref(),
+            "content" => self.content.as_ref(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// Whether responses are recorded or replayed
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureMode {
+    /// Fetch from the network as usual
+    #[default]
+    Off,
+    /// Fetch from the network and save every response
+    Record,
+    /// Serve saved responses instead of the network
+    Replay,
+}
+
+/// Options for recording and replaying responses
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct FixtureOption {
+    #[serde(default)]
+    pub mode: FixtureMode,
+    /// Directory of the fixtures, defaults to `fixtures` in the feed directory
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+}
@@ -0,0 +1,79 @@
+use std::{error::Error, path::PathBuf};
+
+use reqwest::{redirect::Policy, Certificate, Client, ClientBuilder, NoProxy, Proxy};
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// Options for building a HTTP client
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct HttpOption {
+    /// Proxy url for all requests, i.e. http://proxy.local:3128
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Comma separated hosts that should not use the proxy
+    #[serde(rename = "no-proxy")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+    /// Additional PEM root certificates to trust
+    #[serde(rename = "ca-certs")]
+    #[serde(default)]
+    pub ca_certs: Vec<PathBuf>,
+    /// Accept invalid TLS certificates
+    #[serde(rename = "accept-invalid-certs")]
+    #[serde_inline_default(false)]
+    pub accept_invalid_certs: bool,
+    /// Maximum number of redirects to follow
+    #[serde(rename = "max-redirects")]
+    #[serde_inline_default(10)]
+    pub max_redirects: usize,
+    /// Accept gzip compressed responses
+    #[serde_inline_default(true)]
+    pub gzip: bool,
+    /// Accept brotli compressed responses
+    #[serde_inline_default(true)]
+    pub brotli: bool,
+    /// Accept deflate compressed responses
+    #[serde_inline_default(true)]
+    pub deflate: bool,
+    /// Default User-Agent header
+    #[serde(rename = "user-agent")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+}
+
+impl HttpOption {
+    /// Build a client with the options
+    pub fn client(&self) -> Result<Client, Box<dyn Error>> {
+        Ok(self.builder()?.build()?)
+    }
+
+    /// Client builder with the options applied
+    pub fn builder(&self) -> Result<ClientBuilder, Box<dyn Error>> {
+        let mut builder = Client::builder()
+            .gzip(self.gzip)
+            .brotli(self.brotli)
+            .deflate(self.deflate)
+            .redirect(Policy::limited(self.max_redirects))
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+
+        if let Some(proxy) = &self.proxy {
+            let no_proxy = self.no_proxy.as_deref().and_then(NoProxy::from_string);
+            builder = builder.proxy(Proxy::all(proxy)?.no_proxy(no_proxy));
+        }
+
+        for path in self.ca_certs.iter() {
+            builder = builder.add_root_certificate(Certificate::from_pem(&std::fs::read(path)?)?);
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+
+        Ok(builder)
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+use super::ItemFields;
+
+/// Options for the built-in JSON API extractor, fields are JSONPaths from each item
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct JsonOption {
+    /// Path to the items from the response, each match is an item
+    #[serde_inline_default("$[*]".to_string())]
+    pub items: String,
+    /// Path to the next page url or cursor from the response
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    /// Url of the next page with `{next}` replaced by the percent-encoded cursor, the cursor is the url if unset
+    #[serde(rename = "next-url")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_url: Option<String>,
+    /// Paths of each item field, from the item
+    #[serde(flatten)]
+    #[serde(default)]
+    pub fields: ItemFields<String>,
+}
@@ -6,6 +6,8 @@ use serde_inline_default::serde_inline_default;
 
 use crate::traits::Saveable;
 
+use super::{HttpOption, RateLimitOption, RetryOption, RobotsOption};
+
 /// Main config file
 #[serde_inline_default]
 #[derive(Serialize, Deserialize, DefaultFromSerde, Debug)]
@@ -17,6 +19,9 @@ pub struct MasterConfig {
     #[serde(rename = "max-retries")]
     #[serde_inline_default(3)]
     pub max_retries: u16,
+    /// Delays between retries
+    #[serde(default)]
+    pub retry: RetryOption,
     /// Number of seconds before request is considered timed out
     #[serde(rename = "request-timeout")]
     #[serde_inline_default(20)]
@@ -25,6 +30,20 @@ pub struct MasterConfig {
     #[serde(rename = "script-timeout")]
     #[serde_inline_default(20)]
     pub script_timeout: u64,
+    /// Maximum number of bytes in a response body
+    #[serde(rename = "max-body-size")]
+    #[serde_inline_default(52428800)] // 50 MiB
+    pub max_body_size: u64,
+    /// Options for the shared HTTP client
+    #[serde(default)]
+    pub http: HttpOption,
+    /// Politeness options for requests to the same host
+    #[serde(rename = "rate-limit")]
+    #[serde(default)]
+    pub rate_limit: RateLimitOption,
+    /// Options for robots.txt compliance
+    #[serde(default)]
+    pub robots: RobotsOption,
 }
 
 impl Saveable for MasterConfig {}
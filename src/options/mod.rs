@@ -1,7 +1,37 @@
+mod auth;
+mod cookies;
+mod css;
 mod feeds;
 mod fetched;
+mod fields;
+mod fixtures;
+mod http;
+mod json;
 mod master;
+mod pagination;
+mod ratelimit;
+mod regex;
+mod request;
+mod retry;
+mod rhai;
+mod robots;
+mod wasm;
 
+pub use auth::*;
+pub use cookies::*;
+pub use css::*;
 pub use feeds::*;
 pub use fetched::*;
+pub use fields::*;
+pub use fixtures::*;
+pub use http::*;
+pub use json::*;
 pub use master::*;
+pub use pagination::*;
+pub use ratelimit::*;
+pub use regex::*;
+pub use request::*;
+pub use retry::*;
+pub use rhai::*;
+pub use robots::*;
+pub use wasm::*;
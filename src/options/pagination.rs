@@ -0,0 +1,162 @@
+use std::error::Error;
+
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// How the url of each page is found
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum PaginationMode {
+    /// Increment a page number query parameter
+    #[default]
+    Page,
+    /// Increment an offset query parameter by the limit
+    Offset,
+    /// Follow the link of an element matching a CSS selector
+    Next,
+}
+
+/// Pagination driven by scrapyard instead of the extractor's continuation
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct PaginationOption {
+    /// How the url of each page is found
+    #[serde(rename = "type")]
+    #[serde(default)]
+    pub mode: PaginationMode,
+    /// Query parameter holding the page number or offset
+    #[serde_inline_default("page".to_string())]
+    pub param: String,
+    /// First page number or offset, defaults to 1 for pages and 0 for offsets
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
+    /// Number of items per page, added to the offset for each page
+    #[serde_inline_default(20)]
+    pub limit: u64,
+    /// Query parameter holding the limit, not sent if unset
+    #[serde(rename = "limit-param")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_param: Option<String>,
+    /// CSS selector of the link to the next page
+    #[serde_inline_default("a[rel=next]".to_string())]
+    pub selector: String,
+    /// Number of pages fetched at the same time, except when following links
+    #[serde_inline_default(1)]
+    pub concurrency: usize,
+}
+
+impl PaginationOption {
+    /// Url of the page at index (from 0) of the origin
+    pub fn page_url(&self, origin: &str, index: u64) -> Result<String, Box<dyn Error>> {
+        let value = match self.mode {
+            PaginationMode::Offset => self.start.unwrap_or(0) + index * self.limit,
+            _ => self.start.unwrap_or(1) + index,
+        };
+
+        let mut url = Url::parse(origin)?;
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| {
+                key != &self.param && Some(key.as_ref()) != self.limit_param.as_deref()
+            })
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .clear()
+                .extend_pairs(pairs)
+                .append_pair(&self.param, &value.to_string());
+
+            if let Some(limit_param) = &self.limit_param {
+                query.append_pair(limit_param, &self.limit.to_string());
+            }
+        }
+
+        Ok(url.to_string())
+    }
+
+    /// Url of the next page linked from a html page at url
+    pub fn next_url(&self, url: &str, html: &str) -> Result<Option<String>, Box<dyn Error>> {
+        let selector = Selector::parse(&self.selector)
+            .map_err(|e| format!("invalid selector {}: {e}", self.selector))?;
+        let html = Html::parse_document(html);
+
+        let Some(href) = html
+            .select(&selector)
+            .find_map(|element| element.value().attr("href"))
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(Url::parse(url)?.join(href)?.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PaginationMode, PaginationOption};
+
+    #[test]
+    fn pages_count_from_one_by_default() {
+        let option = PaginationOption::default();
+
+        assert_eq!(
+            option.page_url("https://example.com/list", 0).unwrap(),
+            "https://example.com/list?page=1"
+        );
+        assert_eq!(
+            option.page_url("https://example.com/list", 2).unwrap(),
+            "https://example.com/list?page=3"
+        );
+    }
+
+    #[test]
+    fn offsets_step_by_limit_and_replace_existing_params() {
+        let option = PaginationOption {
+            mode: PaginationMode::Offset,
+            param: "offset".to_string(),
+            start: Some(10),
+            limit: 25,
+            limit_param: Some("limit".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            option
+                .page_url("https://example.com/api?q=a+b&offset=5&limit=1", 2)
+                .unwrap(),
+            "https://example.com/api?q=a+b&offset=60&limit=25"
+        );
+    }
+
+    #[test]
+    fn next_link_is_resolved_against_the_page() {
+        let option = PaginationOption {
+            mode: PaginationMode::Next,
+            ..Default::default()
+        };
+        let html = r#"<a href="/x">x</a><a rel="next" href="?page=2">next</a>"#;
+
+        assert_eq!(
+            option
+                .next_url("https://example.com/list?page=1", html)
+                .unwrap()
+                .as_deref(),
+            Some("https://example.com/list?page=2")
+        );
+        assert_eq!(
+            option
+                .next_url("https://example.com/list", "<a href=\"/x\">x</a>")
+                .unwrap(),
+            None
+        );
+        assert!(option.page_url("not a url", 0).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// Politeness options for requests to the same host
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct RateLimitOption {
+    /// Minimum number of milliseconds between requests to a host
+    #[serde(rename = "min-delay")]
+    #[serde_inline_default(1000)]
+    pub min_delay: u64,
+    /// Maximum number of concurrent requests to a host
+    #[serde(rename = "max-connections")]
+    #[serde_inline_default(2)]
+    pub max_connections: usize,
+}
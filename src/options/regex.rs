@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+use super::ItemFields;
+
+/// Options for the built-in regex extractor
+///
+/// Fields are templates with `{name}` replaced by the named capture, the capture with the same name as the field if unset
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct RegexOption {
+    /// Regex applied to the response, each match is an item
+    #[serde_inline_default(String::new())]
+    pub pattern: String,
+    /// Templates of each item field
+    #[serde(flatten)]
+    #[serde(default)]
+    pub fields: ItemFields<String>,
+}
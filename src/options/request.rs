@@ -0,0 +1,65 @@
+use std::{collections::BTreeMap, error::Error};
+
+use reqwest::{Client, Method, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// HTTP request options applied to the origin and every continuation
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct RequestOption {
+    /// HTTP method, i.e. GET or POST
+    #[serde_inline_default("GET".to_string())]
+    pub method: String,
+    /// Additional request headers
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Query parameters appended to the url
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub query: BTreeMap<String, String>,
+    /// Request body, `{url}` is replaced with the url being fetched
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Value of the User-Agent header
+    #[serde(rename = "user-agent")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Send If-None-Match and If-Modified-Since from the previous fetch
+    #[serde_inline_default(true)]
+    pub conditional: bool,
+    /// Pass non-2xx responses to the extractor instead of failing
+    #[serde(rename = "accept-errors")]
+    #[serde_inline_default(false)]
+    pub accept_errors: bool,
+}
+
+impl RequestOption {
+    /// Build a request to url with the options applied
+    pub fn build(&self, client: &Client, url: &str) -> Result<RequestBuilder, Box<dyn Error>> {
+        let method = Method::from_bytes(self.method.to_uppercase().as_bytes())?;
+        let mut request = client.request(method, url);
+
+        if !self.query.is_empty() {
+            request = request.query(&self.query);
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            request = request.header(reqwest::header::USER_AGENT, user_agent);
+        }
+
+        for (key, value) in self.headers.iter() {
+            request = request.header(key, value);
+        }
+
+        if let Some(body) = &self.body {
+            request = request.body(body.replace("{url}", url));
+        }
+
+        Ok(request)
+    }
+}
@@ -0,0 +1,119 @@
+use std::{error::Error, time::Duration};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// Options for waiting between retries
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct RetryOption {
+    /// Number of milliseconds to wait before the first retry, doubled on each retry
+    #[serde(rename = "base-delay")]
+    #[serde_inline_default(1000)]
+    pub base_delay: u64,
+    /// Maximum number of milliseconds to wait between retries
+    #[serde(rename = "max-delay")]
+    #[serde_inline_default(60000)]
+    pub max_delay: u64,
+    /// Randomise delays to avoid retrying in lockstep
+    #[serde_inline_default(true)]
+    pub jitter: bool,
+}
+
+impl RetryOption {
+    /// Delay before retrying after an error on the attempt (from 0), or none if it should not be retried
+    pub fn delay(&self, error: &(dyn Error + 'static), attempt: u16) -> Option<Duration> {
+        match error.downcast_ref::<crate::Error>() {
+            Some(
+                crate::Error::RobotsDisallowed(_)
+                | crate::Error::CrossHostRedirect(_)
+                | crate::Error::LocalFileDenied(_)
+                | crate::Error::BodyTooLarge { .. }
+                | crate::Error::ContinuationLoop { .. }
+                | crate::Error::UnknownExtractor(_)
+                | crate::Error::MissingOption(_),
+            ) => return None,
+            Some(crate::Error::HttpStatus {
+                status,
+                retry_after,
+            }) => {
+                if !matches!(status, 408 | 429 | 500..) {
+                    return None;
+                }
+
+                if let Some(secs) = retry_after {
+                    let delay = secs.saturating_mul(1000);
+                    // the server asked for a longer wait than we are willing to
+                    if delay > self.max_delay {
+                        return None;
+                    }
+
+                    return Some(Duration::from_millis(delay.min(self.max_delay)));
+                }
+            }
+            _ => {}
+        }
+
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << attempt.min(32))
+            .min(self.max_delay);
+
+        Some(Duration::from_millis(if self.jitter {
+            rand::thread_rng().gen_range(delay / 2..=delay)
+        } else {
+            delay
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RetryOption;
+
+    fn status(status: u16, retry_after: Option<u64>) -> crate::Error {
+        crate::Error::HttpStatus {
+            status,
+            retry_after,
+        }
+    }
+
+    #[test]
+    fn retry_after_is_honoured_up_to_max_delay() {
+        let option = RetryOption::default();
+
+        assert_eq!(
+            option.delay(&status(429, Some(3)), 0),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(option.delay(&status(503, Some(61)), 0), None);
+        assert_eq!(option.delay(&status(503, Some(18446744073709552)), 0), None);
+        assert_eq!(option.delay(&status(503, Some(u64::MAX)), 0), None);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max_delay() {
+        let option = RetryOption {
+            jitter: false,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            option.delay(&status(500, None), 0),
+            Some(Duration::from_millis(1000))
+        );
+        assert_eq!(
+            option.delay(&status(500, None), 2),
+            Some(Duration::from_millis(4000))
+        );
+        assert_eq!(
+            option.delay(&status(500, None), 40),
+            Some(Duration::from_millis(60000))
+        );
+        assert_eq!(option.delay(&status(404, None), 0), None);
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// Options for the built-in Rhai script extractor
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct RhaiOption {
+    /// Path to the script
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Script source, used if file is unset
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    /// Maximum number of operations each run, the script fails when exceeded
+    #[serde(rename = "max-operations")]
+    #[serde_inline_default(100_000_000)]
+    pub max_operations: u64,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// Options for robots.txt compliance
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct RobotsOption {
+    /// Check robots.txt before each request
+    #[serde_inline_default(false)]
+    pub enabled: bool,
+    /// Product token matched against user-agent lines in robots.txt
+    #[serde(rename = "user-agent")]
+    #[serde_inline_default(env!("CARGO_PKG_NAME").to_string())]
+    pub user_agent: String,
+    /// Number of seconds a cached robots.txt is kept before fetching again
+    #[serde(rename = "max-age")]
+    #[serde_inline_default(86400)] // 1 day
+    pub max_age: u64,
+}
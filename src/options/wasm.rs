@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use serde_inline_default::serde_inline_default;
+
+/// Options for the built-in WebAssembly extractor
+#[serde_inline_default]
+#[derive(Serialize, Deserialize, DefaultFromSerde, Clone, Debug)]
+pub struct WasmOption {
+    /// Path to a WASI module reading the JSON input from stdin and writing the JSON output to stdout
+    #[serde_inline_default(String::new())]
+    pub module: String,
+    /// Maximum amount of fuel consumed by each run, roughly the number of instructions
+    #[serde_inline_default(10_000_000_000)]
+    pub fuel: u64,
+    /// Maximum number of bytes of linear memory, also limits the output size
+    #[serde(rename = "max-memory")]
+    #[serde_inline_default(64 * 1024 * 1024)]
+    pub max_memory: usize,
+}
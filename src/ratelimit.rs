@@ -0,0 +1,88 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use tokio::{sync::Notify, time::Instant};
+
+use crate::options::RateLimitOption;
+
+#[derive(Default)]
+struct HostState {
+    /// Number of requests in progress
+    active: usize,
+    /// Earliest time the next request may start
+    next: Option<Instant>,
+}
+
+#[derive(Default)]
+struct Host {
+    state: Mutex<HostState>,
+    released: Notify,
+}
+
+/// Host keyed rate limiter shared by all feeds
+pub struct RateLimiter(OnceLock<Mutex<HashMap<String, Arc<Host>>>>);
+
+/// Held while a request to a host is in progress
+pub struct RateLimitPermit(Arc<Host>);
+
+impl RateLimiter {
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Wait until a request to host is allowed under the options
+    pub async fn acquire(&self, host: &str, option: &RateLimitOption) -> RateLimitPermit {
+        let entry = self
+            .0
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap()
+            .entry(host.to_string())
+            .or_default()
+            .clone();
+
+        loop {
+            let released = entry.released.notified();
+
+            let start = {
+                let mut state = entry.state.lock().unwrap();
+                if state.active < option.max_connections.max(1) {
+                    let start = state
+                        .next
+                        .map_or(Instant::now(), |next| next.max(Instant::now()));
+                    state.active += 1;
+                    state.next = Some(start + Duration::from_millis(option.min_delay));
+                    Some(start)
+                } else {
+                    None
+                }
+            };
+
+            match start {
+                Some(start) => {
+                    // permit is created first so the slot is released if cancelled while waiting
+                    let permit = RateLimitPermit(entry.clone());
+                    tokio::time::sleep_until(start).await;
+                    return permit;
+                }
+                None => released.await,
+            }
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for RateLimitPermit {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().active -= 1;
+        self.0.released.notify_waiters();
+    }
+}
@@ -0,0 +1,116 @@
+use std::{collections::HashMap, error::Error, path::Path};
+
+use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE, LAST_MODIFIED},
+    Response, StatusCode, Url,
+};
+use tokio::fs;
+
+use crate::options::CacheValidators;
+
+/// A response before it is decoded for the extractor
+pub struct RawResponse {
+    /// URL after following redirects
+    pub url: String,
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl RawResponse {
+    /// Read a local file as if it was fetched, unchanged files are returned as 304
+    pub async fn read_file(
+        url: &str,
+        known: &HashMap<String, CacheValidators>,
+        limit: u64,
+    ) -> Result<Self, Box<dyn Error>> {
+        let path = Url::parse(url)?
+            .to_file_path()
+            .map_err(|_| crate::Error::FetchFailed)?;
+        let metadata = fs::metadata(&path).await?;
+        if metadata.len() > limit {
+            return Err(crate::Error::BodyTooLarge {
+                url: url.to_string(),
+                limit,
+            }
+            .into());
+        }
+
+        let modified: DateTime<Utc> = metadata.modified()?.into();
+        let modified = modified.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(LAST_MODIFIED, HeaderValue::from_str(&modified)?);
+        if let Some(mime) = mime_from_extension(&path) {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static(mime));
+        }
+
+        let unchanged = known
+            .get(url)
+            .and_then(|cached| cached.last_modified.as_ref())
+            .is_some_and(|last_modified| last_modified == &modified);
+
+        Ok(Self {
+            url: url.to_string(),
+            status: if unchanged {
+                StatusCode::NOT_MODIFIED
+            } else {
+                StatusCode::OK
+            },
+            headers,
+            body: if unchanged {
+                Vec::new()
+            } else {
+                fs::read(&path).await?
+            },
+        })
+    }
+}
+
+/// Read the body of a response, failing once it grows past limit
+///
+/// Errors name url instead of the url of the response, which could hold an auth secret
+pub async fn read_body(
+    mut res: Response,
+    url: &str,
+    limit: u64,
+) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let too_large = || crate::Error::BodyTooLarge {
+        url: url.to_string(),
+        limit,
+    };
+
+    if res.content_length().is_some_and(|length| length > limit) {
+        return Err(too_large().into());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+        if body.len() as u64 + chunk.len() as u64 > limit {
+            return Err(too_large().into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Mime type of common file extensions
+fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+
+    Some(match extension.as_str() {
+        "html" | "htm" => "text/html",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "xml" => "application/xml",
+        "rss" => "application/rss+xml",
+        "atom" => "application/atom+xml",
+        "json" => "application/json",
+        "eml" | "mbox" => "message/rfc822",
+        "pdf" => "application/pdf",
+        _ => return None,
+    })
+}
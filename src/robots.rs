@@ -0,0 +1,324 @@
+use std::{error::Error, path::PathBuf, time::Duration};
+
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::{
+    options::{RateLimitOption, RobotsOption},
+    traits::Saveable,
+    values::{LIMITER, MASTER},
+};
+
+/// Parsed robots.txt of a host
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct RobotsTxt {
+    /// Fetched timestamp
+    pub fetched: u64,
+    /// Groups of rules in the order they appear
+    pub groups: Vec<RobotsGroup>,
+}
+
+impl Saveable for RobotsTxt {}
+
+/// Rules that apply to a set of user agents
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct RobotsGroup {
+    /// User agent product tokens, lowercased
+    #[serde(rename = "user-agents")]
+    pub user_agents: Vec<String>,
+    /// Allow and disallow rules
+    pub rules: Vec<RobotsRule>,
+    /// Number of seconds between requests
+    #[serde(rename = "crawl-delay")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crawl_delay: Option<f64>,
+}
+
+/// A single allow or disallow rule
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RobotsRule {
+    pub allow: bool,
+    pub path: String,
+}
+
+impl RobotsTxt {
+    /// Parse the content of a robots.txt file
+    pub fn parse(s: &str) -> Self {
+        let mut groups: Vec<RobotsGroup> = Vec::new();
+        // whether the last group is still collecting user-agent lines
+        let mut collecting = false;
+
+        for line in s.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key.trim().to_lowercase().as_str() {
+                "user-agent" => {
+                    if !collecting {
+                        groups.push(RobotsGroup::default());
+                        collecting = true;
+                    }
+                    groups
+                        .last_mut()
+                        .unwrap()
+                        .user_agents
+                        .push(value.to_lowercase());
+                }
+                key @ ("allow" | "disallow") => {
+                    collecting = false;
+                    // an empty disallow allows everything
+                    if let Some(group) = groups.last_mut().filter(|_| !value.is_empty()) {
+                        group.rules.push(RobotsRule {
+                            allow: key == "allow",
+                            path: value.to_string(),
+                        });
+                    }
+                }
+                "crawl-delay" => {
+                    collecting = false;
+                    if let Some(group) = groups.last_mut() {
+                        group.crawl_delay = value.parse().ok();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            fetched: chrono::Utc::now().timestamp() as u64,
+            groups,
+        }
+    }
+
+    /// A robots.txt that disallows everything
+    pub fn disallow_all() -> Self {
+        Self {
+            fetched: chrono::Utc::now().timestamp() as u64,
+            groups: vec![RobotsGroup {
+                user_agents: vec!["*".to_string()],
+                rules: vec![RobotsRule {
+                    allow: false,
+                    path: "/".to_string(),
+                }],
+                crawl_delay: None,
+            }],
+        }
+    }
+
+    /// Groups that apply to the user agent, falling back to the `*` groups
+    fn groups(&self, agent: &str) -> Vec<&RobotsGroup> {
+        let agent = agent.to_lowercase();
+        let matched: Vec<&RobotsGroup> = self
+            .groups
+            .iter()
+            .filter(|group| group.user_agents.iter().any(|ua| ua == &agent))
+            .collect();
+
+        if !matched.is_empty() {
+            return matched;
+        }
+
+        self.groups
+            .iter()
+            .filter(|group| group.user_agents.iter().any(|ua| ua == "*"))
+            .collect()
+    }
+
+    /// Check if the user agent may fetch the path, the longest matching rule wins
+    pub fn allowed(&self, agent: &str, path: &str) -> bool {
+        if path == "/robots.txt" {
+            return true;
+        }
+
+        self.groups(agent)
+            .into_iter()
+            .flat_map(|group| group.rules.iter())
+            .filter(|rule| pattern_matches(&rule.path, path))
+            .max_by_key(|rule| (rule.path.len(), rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+
+    /// Crawl delay for the user agent in seconds
+    pub fn crawl_delay(&self, agent: &str) -> Option<f64> {
+        self.groups(agent)
+            .into_iter()
+            .filter_map(|group| group.crawl_delay)
+            .reduce(f64::max)
+    }
+
+    /// Load robots.txt for the host of url, from cache or remote
+    pub async fn load(
+        client: &Client,
+        url: &Url,
+        option: &RobotsOption,
+        rate_limit: &RateLimitOption,
+    ) -> Result<Self, Box<dyn Error>> {
+        let path = Self::cache_path(url);
+
+        if fs::try_exists(&path).await? {
+            if let Ok(robots) = Self::load_json(&path).await {
+                if robots.fetched + option.max_age > chrono::Utc::now().timestamp() as u64 {
+                    return Ok(robots);
+                }
+            }
+        }
+
+        let mut robots_url = url.clone();
+        robots_url.set_path("/robots.txt");
+        robots_url.set_query(None);
+        robots_url.set_fragment(None);
+
+        let host = url.host_str().unwrap_or_default();
+        let permit = LIMITER.acquire(host, rate_limit).await;
+        let res = tokio::select! {
+            res = async {
+                let res = client.get(robots_url).send().await?;
+                let status = res.status();
+                Ok::<_, reqwest::Error>((status, res.text().await?))
+            } => {
+                res?
+            },
+            _ = tokio::time::sleep(Duration::from_secs(MASTER.get().unwrap().request_timeout)) => {
+                return Err(crate::Error::Timedout.into());
+            }
+        };
+        drop(permit);
+
+        let robots = Self::from_response(res.0, &res.1);
+        robots.save_json(&path).await?;
+        Ok(robots)
+    }
+
+    /// Rules from the status and content of a robots.txt response
+    fn from_response(status: StatusCode, content: &str) -> Self {
+        if status.is_success() {
+            Self::parse(content)
+        } else if status.is_client_error() {
+            // unavailable robots.txt means there are no restrictions
+            Self::parse("")
+        } else {
+            // unreachable robots.txt means everything is disallowed
+            Self::disallow_all()
+        }
+    }
+
+    /// Path to the cached robots.txt of the host of url
+    fn cache_path(url: &Url) -> PathBuf {
+        MASTER.get().unwrap().store.join(".robots").join(format!(
+            "{}-{}-{}.json",
+            url.scheme(),
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().unwrap_or_default()
+        ))
+    }
+}
+
+/// Match a robots.txt path pattern, supporting `*` wildcards and `$` end anchors
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        if anchored && i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    !anchored || rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use super::{pattern_matches, RobotsTxt};
+
+    #[test]
+    fn groups_collect_consecutive_user_agents() {
+        let robots = RobotsTxt::parse(
+            "User-agent: a\nUser-agent: B # comment\nDisallow: /x\nCrawl-delay: 2\n\nUser-agent: *\nDisallow:\nAllow: /y\n",
+        );
+
+        assert_eq!(robots.groups.len(), 2);
+        assert_eq!(robots.groups[0].user_agents, ["a", "b"]);
+        assert_eq!(robots.groups[0].rules.len(), 1);
+        assert_eq!(robots.groups[0].crawl_delay, Some(2.0));
+        // the empty disallow is not a rule
+        assert_eq!(robots.groups[1].rules.len(), 1);
+        assert!(robots.groups[1].rules[0].allow);
+    }
+
+    #[test]
+    fn user_agent_group_replaces_wildcard_group() {
+        let robots = RobotsTxt::parse(
+            "User-agent: *\nDisallow: /\nCrawl-delay: 5\n\nUser-agent: scrapyard\nDisallow: /private\n",
+        );
+
+        assert!(robots.allowed("Scrapyard", "/public"));
+        assert!(!robots.allowed("scrapyard", "/private/page"));
+        assert!(!robots.allowed("other", "/public"));
+        assert_eq!(robots.crawl_delay("scrapyard"), None);
+        assert_eq!(robots.crawl_delay("other"), Some(5.0));
+    }
+
+    #[test]
+    fn longest_match_wins_and_allow_wins_ties() {
+        let robots = RobotsTxt::parse(
+            "User-agent: *\nDisallow: /a\nAllow: /a/b\nDisallow: /a/b/c\nDisallow: /t\nAllow: /t\n",
+        );
+
+        assert!(!robots.allowed("x", "/a/x"));
+        assert!(robots.allowed("x", "/a/b/x"));
+        assert!(!robots.allowed("x", "/a/b/c"));
+        assert!(robots.allowed("x", "/t"));
+        assert!(robots.allowed("x", "/other"));
+    }
+
+    #[test]
+    fn robots_txt_is_always_allowed() {
+        assert!(RobotsTxt::disallow_all().allowed("x", "/robots.txt"));
+        assert!(!RobotsTxt::disallow_all().allowed("x", "/"));
+    }
+
+    #[test]
+    fn patterns_match_wildcards_and_anchors() {
+        assert!(pattern_matches("/a", "/abc"));
+        assert!(!pattern_matches("/a", "/b/a"));
+        assert!(pattern_matches("/*.php", "/dir/index.php?x=1"));
+        assert!(pattern_matches("/*.php$", "/dir/index.php"));
+        assert!(!pattern_matches("/*.php$", "/dir/index.php?x=1"));
+        assert!(pattern_matches("/a*b*c", "/axxbyyc"));
+        assert!(!pattern_matches("/a*b*c", "/axxcyyb"));
+        assert!(pattern_matches("/a$", "/a"));
+        assert!(!pattern_matches("/a$", "/ab"));
+        assert!(pattern_matches("*", "/anything"));
+    }
+
+    #[test]
+    fn unavailable_allows_and_unreachable_disallows() {
+        let content = "User-agent: *\nDisallow: /x\n";
+
+        assert!(!RobotsTxt::from_response(StatusCode::OK, content).allowed("a", "/x"));
+        assert!(RobotsTxt::from_response(StatusCode::NOT_FOUND, content).allowed("a", "/x"));
+        assert!(RobotsTxt::from_response(StatusCode::FORBIDDEN, "").allowed("a", "/"));
+        assert!(!RobotsTxt::from_response(StatusCode::SERVICE_UNAVAILABLE, "").allowed("a", "/"));
+    }
+}
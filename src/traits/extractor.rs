@@ -0,0 +1,10 @@
+use std::error::Error;
+
+use crate::{ItemizerArg, ItemizerRes};
+
+/// Extracts items from a page in process, instead of running an extractor command
+#[async_trait::async_trait]
+pub trait Extractor: Send + Sync {
+    /// Extract items from the page, same as the output of an extractor command
+    async fn extract(&self, arg: &ItemizerArg) -> Result<ItemizerRes, Box<dyn Error>>;
+}
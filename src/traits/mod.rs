@@ -1,2 +1,4 @@
+mod extractor;
+pub use extractor::*;
 mod saveable;
 pub use saveable::*;
@@ -2,16 +2,32 @@ use std::path::Path;
 use std::process;
 use std::sync::OnceLock;
 
+use reqwest::Client;
+
+use crate::clients::Clients;
+use crate::extractors::Extractors;
 use crate::locks::Locks;
 use crate::options::MasterConfig;
+use crate::ratelimit::RateLimiter;
 use crate::traits::Saveable;
+use crate::workers::Workers;
 
 /// Self identifier of the crate: `scrapyard X.Y.Z (git 123abcd)`
 pub static IDENT: OnceLock<String> = OnceLock::new();
 /// Holds global master config
 pub static MASTER: OnceLock<MasterConfig> = OnceLock::new();
 /// Fetch locks to avoid duplicated fetching
-pub static mut LOCKS: Locks = Locks::new();
+pub static LOCKS: Locks = Locks::new();
+/// Shared HTTP client built from the master config
+pub static CLIENT: OnceLock<Client> = OnceLock::new();
+/// HTTP clients of feeds with their own http options
+pub static CLIENTS: Clients = Clients::new();
+/// Per host rate limiter shared by all feeds
+pub static LIMITER: RateLimiter = RateLimiter::new();
+/// In process extractors registered by name
+pub static EXTRACTORS: Extractors = Extractors::new();
+/// Long running extractors of feeds in worker mode
+pub static WORKERS: Workers = Workers::new();
 
 /// Initialise all OnceLocks
 pub async fn init(config: Option<&Path>) {
@@ -41,5 +57,14 @@ pub async fn init(config: Option<&Path>) {
         default
     };
 
+    let client = match master.http.client() {
+        Ok(client) => client,
+        Err(e) => {
+            println!("Could not build HTTP client\nError: {e}");
+            process::exit(0);
+        }
+    };
+
+    CLIENT.set(client).unwrap();
     MASTER.set(master).unwrap();
 }
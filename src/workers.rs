@@ -0,0 +1,151 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    io,
+    process::Stdio,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    process::{Child, ChildStdin, ChildStdout, Command},
+    sync::Mutex,
+};
+
+use crate::{ItemizerArg, ItemizerRes};
+
+/// Worker of a command, none if it has not started or was stopped
+type Slot = Arc<Mutex<Option<Worker>>>;
+
+/// Long running extractor processes, one for each command
+pub struct Workers(OnceLock<std::sync::Mutex<HashMap<Vec<String>, Slot>>>);
+
+impl Workers {
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Send a page to the worker running command, (re)starting it if it is not healthy
+    pub async fn request(
+        &self,
+        command: &[String],
+        arg: &ItemizerArg,
+        timeout: Duration,
+    ) -> Result<ItemizerRes, Box<dyn Error>> {
+        let slot = self
+            .0
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap()
+            .entry(command.to_vec())
+            .or_default()
+            .clone();
+        let mut worker = slot.lock().await;
+
+        let healthy = match worker.as_mut() {
+            Some(worker) => worker.healthy(timeout).await,
+            None => false,
+        };
+
+        // the old process is killed when dropped
+        if !healthy {
+            *worker = Some(Worker::spawn(command, timeout).await?);
+        }
+
+        let line = serde_json::to_vec(arg)?;
+        let reply = match worker.as_mut().unwrap().exchange(&line, timeout).await {
+            Ok(reply) => reply,
+            Err(e) => {
+                // the output can no longer be matched to requests, restart on the next request
+                *worker = None;
+                return Err(e.into());
+            }
+        };
+
+        match serde_json::from_str(&reply) {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                println!("Could not deserialize worker output: {e}");
+                println!("Worker stdout:\n{reply}");
+                Err(e.into())
+            }
+        }
+    }
+}
+
+impl Default for Workers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Extractor process exchanging newline delimited JSON over stdin and stdout
+struct Worker {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Worker {
+    /// Start the command and wait for it to answer a health check
+    async fn spawn(command: &[String], timeout: Duration) -> io::Result<Self> {
+        let mut child = Command::new(command.first().unwrap())
+            .args(&command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let mut worker = Self {
+            stdin: child.stdin.take().unwrap(),
+            stdout: BufReader::new(child.stdout.take().unwrap()),
+            child,
+        };
+
+        if !worker.healthy(timeout).await {
+            return Err(io::Error::other(format!(
+                "worker {command:?} did not answer the health check"
+            )));
+        }
+
+        Ok(worker)
+    }
+
+    /// Check if the process is running and answers `{"ping":true}` with `{"pong":true}`
+    async fn healthy(&mut self, timeout: Duration) -> bool {
+        if !matches!(self.child.try_wait(), Ok(None)) {
+            return false;
+        }
+
+        match self.exchange(br#"{"ping":true}"#, timeout).await {
+            Ok(reply) => serde_json::from_str::<serde_json::Value>(&reply)
+                .is_ok_and(|reply| reply["pong"] == true),
+            Err(_) => false,
+        }
+    }
+
+    /// Write a line to stdin and read a line from stdout
+    async fn exchange(&mut self, line: &[u8], timeout: Duration) -> io::Result<String> {
+        let exchange = async {
+            self.stdin.write_all(line).await?;
+            self.stdin.write_all(b"\n").await?;
+            self.stdin.flush().await?;
+
+            let mut reply = String::new();
+            if self.stdout.read_line(&mut reply).await? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "worker exited",
+                ));
+            }
+
+            Ok(reply)
+        };
+
+        tokio::select! {
+            reply = exchange => reply,
+            _ = tokio::time::sleep(timeout) => Err(io::ErrorKind::TimedOut.into())
+        }
+    }
+}